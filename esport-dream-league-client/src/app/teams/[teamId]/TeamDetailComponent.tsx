@@ -135,6 +135,9 @@ export default function TeamDetailComponent({ teamId }: TeamDetailComponentProps
             avgMechanical: 0,
             avgGameKnowledge: 0,
             avgTeamCommunication: 0,
+            avgAdaptability: 0,
+            avgConsistency: 0,
+            overallRating: 0,
             synergyScore: 0
           },
           matchHistory: teamAccount.matchHistory || []
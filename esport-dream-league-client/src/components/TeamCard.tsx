@@ -5,6 +5,15 @@ import { RosterPosition, TeamStatistics } from '@/types/program-types';
 import Image from 'next/image';
 import { PublicKey } from '@solana/web3.js';
 
+// Roster averages computed on-chain from the rostered players
+const STAT_AVERAGES: { label: string; key: keyof TeamStatistics }[] = [
+  { label: 'Mechanical', key: 'avgMechanical' },
+  { label: 'Game Knowledge', key: 'avgGameKnowledge' },
+  { label: 'Communication', key: 'avgTeamCommunication' },
+  { label: 'Adaptability', key: 'avgAdaptability' },
+  { label: 'Consistency', key: 'avgConsistency' },
+];
+
 interface TeamCardProps {
   name: string;
   logoUri: string;
@@ -40,7 +49,12 @@ export const TeamCard: FC<TeamCardProps> = ({
               />
             )}
           </div>
-          <h2 className="text-xl font-bold">{name}</h2>
+          <div>
+            <h2 className="text-xl font-bold">{name}</h2>
+            {roster.length > 0 && (
+              <p className="text-sm text-gray-500">Overall {statistics.overallRating}</p>
+            )}
+          </div>
         </div>
         
         <div className="mb-6">
@@ -65,6 +79,26 @@ export const TeamCard: FC<TeamCardProps> = ({
           </div>
         </div>
         
+        {roster.length > 0 && (
+          <div className="mb-6">
+            <h3 className="text-lg font-semibold mb-2">Roster Averages</h3>
+            {STAT_AVERAGES.map(({ label, key }) => (
+              <div key={key} className="mb-2">
+                <div className="flex justify-between mb-1 text-sm">
+                  <span>{label}</span>
+                  <span>{statistics[key]}</span>
+                </div>
+                <div className="w-full bg-gray-200 rounded-full h-2">
+                  <div
+                    className="bg-indigo-500 h-2 rounded-full"
+                    style={{ width: `${statistics[key]}%` }}
+                  ></div>
+                </div>
+              </div>
+            ))}
+          </div>
+        )}
+        
         <div className="mb-6">
           <h3 className="text-lg font-semibold mb-2">Team Synergy</h3>
           <div className="mb-2">
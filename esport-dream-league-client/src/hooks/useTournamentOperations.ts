@@ -3,7 +3,7 @@
 
 import { useState } from 'react';
 import { useConnection, useWallet } from '@solana/wallet-adapter-react';
-import { useProgram, findTournamentPDA } from '@/contexts/ProgramContextProvider';
+import { useProgram, findTournamentPDA, findPlayerPDA } from '@/contexts/ProgramContextProvider';
 import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
 import { toast } from 'react-toastify';
 import * as anchor from '@project-serum/anchor';
@@ -107,6 +107,18 @@ export function useTournamentOperations() {
     setIsLoading(true);
     
     try {
+      // Both rosters go in as remaining accounts (winner first) so the program can
+      // credit every rostered pair with the shared match
+      const [winnerTeam, loserTeam] = await Promise.all([
+        program.account.teamAccount.fetch(winnerTeamPDA),
+        program.account.teamAccount.fetch(loserTeamPDA),
+      ]) as unknown as [TeamAccount, TeamAccount];
+      const rosterAccounts = [...winnerTeam.roster, ...loserTeam.roster].map((position) => ({
+        pubkey: findPlayerPDA(position.playerMint)[0],
+        isWritable: false,
+        isSigner: false,
+      }));
+      
       const tx = await program.methods
         .recordMatchResult(
           matchId,
@@ -121,6 +133,7 @@ export function useTournamentOperations() {
           winnerTeam: winnerTeamPDA,
           loserTeam: loserTeamPDA,
         })
+        .remainingAccounts(rosterAccounts)
         .transaction();
       
       const signature = await sendTransaction(tx, connection);
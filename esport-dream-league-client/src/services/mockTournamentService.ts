@@ -76,6 +76,9 @@ export const generateMockTeams = (): TeamAccount[] => {
       avgMechanical: 85,
       avgGameKnowledge: 82,
       avgTeamCommunication: 78,
+      avgAdaptability: 80,
+      avgConsistency: 83,
+      overallRating: 81,
       synergyScore: 88
     },
     matchHistory: []
@@ -98,6 +101,9 @@ export const generateMockTeams = (): TeamAccount[] => {
       avgMechanical: 78,
       avgGameKnowledge: 75,
       avgTeamCommunication: 82,
+      avgAdaptability: 74,
+      avgConsistency: 76,
+      overallRating: 77,
       synergyScore: 80
     },
     matchHistory: []
@@ -43,6 +43,7 @@ export interface TeamAccount {
   roster: RosterPosition[]; // Players in the team
   statistics: TeamStatistics;
   matchHistory?: TeamMatchResult[];
+  synergyPairs?: SynergyPair[];
 }
 
 // Reuse your existing types or define them here
@@ -60,9 +61,19 @@ export interface TeamStatistics {
   avgMechanical: number;
   avgGameKnowledge: number;
   avgTeamCommunication: number;
+  avgAdaptability: number;
+  avgConsistency: number;
+  overallRating: number;
   synergyScore: number;
 }
 
+// Chemistry between two rostered players, keyed by a hash of both mints
+export interface SynergyPair {
+  pairKey: number[];
+  matchesTogether: number;
+  synergy: number;
+}
+
 export interface TeamMatchResult {
   matchId: string;
   timestamp: number;
@@ -253,6 +253,7 @@ export const IDL: Idl = {
           { name: "roster", type: { vec: { defined: "RosterPosition" } } },
           { name: "statistics", type: { defined: "TeamStatistics" } },
           { name: "matchHistory", type: { vec: { defined: "TeamMatchResult" } } },
+          { name: "synergyPairs", type: { vec: { defined: "SynergyPair" } } },
         ],
       },
     },
@@ -339,10 +340,24 @@ export const IDL: Idl = {
           { name: "avgMechanical", type: "u8" },
           { name: "avgGameKnowledge", type: "u8" },
           { name: "avgTeamCommunication", type: "u8" },
+          { name: "avgAdaptability", type: "u8" },
+          { name: "avgConsistency", type: "u8" },
+          { name: "overallRating", type: "u8" },
           { name: "synergyScore", type: "u8" }
         ]
       }
     },
+    {
+      name: "SynergyPair",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "pairKey", type: { array: ["u8", 8] } },
+          { name: "matchesTogether", type: "u16" },
+          { name: "synergy", type: "u8" }
+        ]
+      }
+    },
     {
       name: "TeamMatchResult",
       type: {
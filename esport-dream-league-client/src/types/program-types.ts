@@ -59,6 +59,9 @@ export type TeamStatistics = {
   avgMechanical: number;
   avgGameKnowledge: number;
   avgTeamCommunication: number;
+  avgAdaptability: number;
+  avgConsistency: number;
+  overallRating: number;
   synergyScore: number;
 };
 
@@ -73,6 +76,13 @@ export type MatchPerformance = {
 };
 
 // ✅ Team Match Result
+// Chemistry between two rostered players, keyed by a hash of both mints
+export type SynergyPair = {
+  pairKey: number[];
+  matchesTogether: number;
+  synergy: number;
+};
+
 export type TeamMatchResult = {
   matchId: string;
   timestamp: number;
@@ -137,6 +147,7 @@ export interface TeamAccount {
   roster: RosterPosition[]; // Players in the team
   statistics: TeamStatistics;
   matchHistory?: TeamMatchResult[];
+  synergyPairs?: SynergyPair[];
 }
 
 // Added TournamentAccount based on the IDL
@@ -0,0 +1,72 @@
+//! Serves player metadata JSON at the path the NFT `uri` points to.
+//!
+//! Usage:
+//!   player-metadata-server [--listen 0.0.0.0:8080] (--rpc <url> | --accounts-dir <dir>)
+//!                          [--image-base-url <url>] [--external-url <url>] [--symbol <symbol>]
+
+use player_metadata::{handle_request, AccountSource, DirectorySource, RenderConfig, RpcSource};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, Response, Server};
+
+fn main() {
+    let mut listen = "0.0.0.0:8080".to_string();
+    let mut rpc_url = None;
+    let mut accounts_dir = None;
+    let mut config = RenderConfig::default();
+    
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage(&format!("missing value for {}", flag)));
+        match flag.as_str() {
+            "--listen" => listen = value,
+            "--rpc" => rpc_url = Some(value),
+            "--accounts-dir" => accounts_dir = Some(value),
+            "--image-base-url" => config.image_base_url = value,
+            "--external-url" => config.external_url = value,
+            "--symbol" => config.symbol = value,
+            _ => usage(&format!("unknown flag {}", flag)),
+        }
+    }
+    
+    let source: Box<dyn AccountSource> = match (rpc_url, accounts_dir) {
+        (Some(url), None) => Box::new(RpcSource::new(url)),
+        (None, Some(dir)) => Box::new(DirectorySource::new(dir)),
+        _ => usage("exactly one of --rpc or --accounts-dir is required"),
+    };
+    
+    let server = Server::http(&listen).unwrap_or_else(|err| {
+        eprintln!("failed to listen on {}: {}", listen, err);
+        std::process::exit(1);
+    });
+    println!("serving player metadata on {}", listen);
+    
+    for request in server.incoming_requests() {
+        if *request.method() != Method::Get {
+            let _ = request.respond(Response::from_string("").with_status_code(405));
+            continue;
+        }
+        
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+        let response = handle_request(request.url(), source.as_ref(), &config, now);
+        
+        let content_type = Header::from_bytes("Content-Type", "application/json")
+            .expect("static header is valid");
+        let _ = request.respond(
+            Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(content_type),
+        );
+    }
+}
+
+fn usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!(
+        "usage: player-metadata-server [--listen <addr>] (--rpc <url> | --accounts-dir <dir>) \
+         [--image-base-url <url>] [--external-url <url>] [--symbol <symbol>]"
+    );
+    std::process::exit(2);
+}
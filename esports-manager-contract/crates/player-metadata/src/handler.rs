@@ -0,0 +1,90 @@
+use crate::render::{decode_player, render_player, RenderConfig};
+use crate::source::AccountSource;
+use anchor_lang::prelude::Pubkey;
+use esports_manager_contract::utils::pda::find_player_address;
+use std::str::FromStr;
+
+// Plain HTTP response, independent of the server implementation
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+// Route a player's metadata is served at
+pub fn player_path(mint: &Pubkey) -> String {
+    format!("/player/{}.json", mint)
+}
+
+// NFT uri to mint a player with so marketplaces fetch it from this server
+// (`base_url` is where the server is reachable, e.g. `https://5vs5.gg`)
+pub fn player_uri(base_url: &str, mint: &Pubkey) -> String {
+    format!("{}{}", base_url.trim_end_matches('/'), player_path(mint))
+}
+
+// Path part of a uri, without scheme, host or query
+fn uri_path(uri: &str) -> &str {
+    let without_query = uri.split('?').next().unwrap_or_default();
+    match without_query.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => without_query,
+    }
+}
+
+// Serve `GET /player/<mint>.json` (query strings such as `?rev=3` are ignored)
+// Only players whose on-chain `uri` points at that path are served
+pub fn handle_request(
+    path: &str,
+    source: &dyn AccountSource,
+    config: &RenderConfig,
+    now: i64,
+) -> Response {
+    let path = path.split('?').next().unwrap_or_default();
+    
+    let mint = match path.strip_prefix("/player/") {
+        Some(rest) => rest.trim_end_matches(".json"),
+        None => return Response::error(404, "not found"),
+    };
+    
+    let mint = match Pubkey::from_str(mint) {
+        Ok(mint) => mint,
+        Err(_) => return Response::error(400, "invalid mint address"),
+    };
+    
+    let (address, _) = find_player_address(&mint, &esports_manager_contract::ID);
+    
+    let data = match source.fetch(&address) {
+        Ok(Some(data)) => data,
+        Ok(None) => return Response::error(404, "player not found"),
+        Err(err) => return Response::error(502, &err),
+    };
+    
+    let player = match decode_player(&data) {
+        Ok(player) => player,
+        Err(err) => return Response::error(422, &err),
+    };
+    
+    // Guard against a dump stored under the wrong address
+    if player.mint != mint {
+        return Response::error(422, "player account does not match mint");
+    }
+    
+    // The NFT uri decides where its metadata lives; don't answer for players hosted elsewhere
+    if uri_path(&player.uri) != path {
+        return Response::error(404, "player metadata is not served at this path");
+    }
+    
+    match serde_json::to_string_pretty(&render_player(&player, config, now)) {
+        Ok(body) => Response { status: 200, body },
+        Err(err) => Response::error(500, &err.to_string()),
+    }
+}
@@ -0,0 +1,12 @@
+//! Off-chain renderer for player NFT metadata.
+//!
+//! Deserializes a `PlayerAccount` and renders Metaplex-standard JSON so the
+//! NFT `uri` always reflects on-chain state instead of hand-maintained files.
+
+pub mod handler;
+pub mod render;
+pub mod source;
+
+pub use handler::{handle_request, player_path, player_uri, Response};
+pub use render::{render_player, MetadataAttribute, PlayerMetadata, RenderConfig};
+pub use source::{AccountSource, DirectorySource, RpcSource};
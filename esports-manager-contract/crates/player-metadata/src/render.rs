@@ -0,0 +1,229 @@
+use anchor_lang::AccountDeserialize;
+use esports_manager_contract::ability::{
+    CLUTCH_FACTOR_ID, CREATOR_SIGNATURE_ID, PERFECT_EXECUTION_ID, SHOT_CALLER_ID,
+};
+use esports_manager_contract::player::{PlayerAccount, Position, Rarity};
+use serde::Serialize;
+use serde_json::Value;
+
+// Static settings for rendering (where images and the game site live)
+#[derive(Clone, Debug)]
+pub struct RenderConfig {
+    pub symbol: String,
+    pub image_base_url: String,
+    pub external_url: String,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            symbol: "5VS5".to_string(),
+            image_base_url: "https://5vs5.gg/images/players".to_string(),
+            external_url: "https://5vs5.gg/players".to_string(),
+        }
+    }
+}
+
+// Metaplex token metadata standard JSON
+#[derive(Debug, Serialize)]
+pub struct PlayerMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub image: String,
+    pub external_url: String,
+    pub attributes: Vec<MetadataAttribute>,
+    pub properties: MetadataProperties,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetadataAttribute {
+    pub trait_type: String,
+    pub value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetadataProperties {
+    pub category: String,
+    pub files: Vec<MetadataFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetadataFile {
+    pub uri: String,
+    #[serde(rename = "type")]
+    pub file_type: String,
+}
+
+impl MetadataAttribute {
+    fn text(trait_type: &str, value: impl Into<String>) -> Self {
+        Self {
+            trait_type: trait_type.to_string(),
+            value: Value::String(value.into()),
+            display_type: None,
+            max_value: None,
+        }
+    }
+    
+    fn number(trait_type: &str, value: u64) -> Self {
+        Self {
+            trait_type: trait_type.to_string(),
+            value: Value::from(value),
+            display_type: Some("number".to_string()),
+            max_value: None,
+        }
+    }
+    
+    // 0-100 rated stat
+    fn rating(trait_type: &str, value: u8) -> Self {
+        Self {
+            max_value: Some(100),
+            ..Self::number(trait_type, value as u64)
+        }
+    }
+}
+
+// Deserialize raw account data (with discriminator) into a player
+pub fn decode_player(data: &[u8]) -> Result<PlayerAccount, String> {
+    PlayerAccount::try_deserialize(&mut &data[..])
+        .map_err(|err| format!("not a player account: {}", err))
+}
+
+// Render a player's metadata as of `now` (age and form are time dependent)
+pub fn render_player(player: &PlayerAccount, config: &RenderConfig, now: i64) -> PlayerMetadata {
+    let mint = player.mint.to_string();
+    let image = format!("{}/{}.png", config.image_base_url, mint);
+    
+    PlayerMetadata {
+        name: player.name.clone(),
+        symbol: config.symbol.clone(),
+        description: format!(
+            "{}, a {} {} in the 5vs5.gg esports league.",
+            player.name,
+            rarity_name(&player.rarity).to_lowercase(),
+            position_name(&player.position),
+        ),
+        image: image.clone(),
+        external_url: format!("{}/{}", config.external_url, mint),
+        attributes: player_attributes(player, now),
+        properties: MetadataProperties {
+            category: "image".to_string(),
+            files: vec![MetadataFile {
+                uri: image,
+                file_type: "image/png".to_string(),
+            }],
+        },
+    }
+}
+
+// Core stats, rarity, abilities, record, team and creator as attributes
+pub fn player_attributes(player: &PlayerAccount, now: i64) -> Vec<MetadataAttribute> {
+    let mut attributes = vec![
+        MetadataAttribute::text("Position", position_name(&player.position)),
+        MetadataAttribute::text("Rarity", rarity_name(&player.rarity)),
+        MetadataAttribute::text("Status", status_name(player, now)),
+        MetadataAttribute::rating("Mechanical", player.mechanical),
+        MetadataAttribute::rating("Game Knowledge", player.game_knowledge),
+        MetadataAttribute::rating("Team Communication", player.team_communication),
+        MetadataAttribute::rating("Adaptability", player.adaptability),
+        MetadataAttribute::rating("Consistency", player.consistency),
+        potential_attribute(player),
+        MetadataAttribute::rating("Form", player.current_form(now)),
+        MetadataAttribute::rating("Morale", player.morale),
+        MetadataAttribute::number("Age", player.age(now) as u64),
+        MetadataAttribute::number("Experience", player.experience as u64),
+        MetadataAttribute::number("Matches Played", player.matches_played as u64),
+        MetadataAttribute::number("Wins", player.wins as u64),
+        MetadataAttribute::number(
+            "Losses",
+            player.matches_played.saturating_sub(player.wins) as u64,
+        ),
+        MetadataAttribute::number("MVP Awards", player.mvp_count as u64),
+    ];
+    
+    for ability in &player.special_abilities {
+        attributes.push(MetadataAttribute::number(
+            &ability_name(ability.ability_id),
+            ability.level as u64,
+        ));
+    }
+    
+    if let Some(hero) = player.game_specific_data.comfort_pick() {
+        attributes.push(MetadataAttribute::text("Comfort Pick", format!("Hero #{}", hero.hero_id)));
+    }
+    
+    attributes.push(MetadataAttribute::text(
+        "Team",
+        player.team.map(|team| team.to_string()).unwrap_or_else(|| "Free Agent".to_string()),
+    ));
+    
+    if let Some(creator) = player.creator {
+        attributes.push(MetadataAttribute::text("Creator", creator.to_string()));
+    }
+    attributes.push(MetadataAttribute::text(
+        "Exclusive",
+        if player.is_exclusive { "Yes" } else { "No" },
+    ));
+    
+    attributes
+}
+
+// Hidden potential only shows its public range
+fn potential_attribute(player: &PlayerAccount) -> MetadataAttribute {
+    if player.potential_revealed {
+        MetadataAttribute::rating("Potential", player.potential)
+    } else {
+        MetadataAttribute::text(
+            "Potential",
+            format!("{}-{}", player.potential_min, player.potential_max),
+        )
+    }
+}
+
+pub fn position_name(position: &Position) -> &'static str {
+    match position {
+        Position::Top => "Top",
+        Position::Jungle => "Jungle",
+        Position::Mid => "Mid",
+        Position::Adc => "ADC",
+        Position::Support => "Support",
+        Position::Flex => "Flex",
+    }
+}
+
+pub fn rarity_name(rarity: &Rarity) -> &'static str {
+    match rarity {
+        Rarity::Common => "Common",
+        Rarity::Uncommon => "Uncommon",
+        Rarity::Rare => "Rare",
+        Rarity::Epic => "Epic",
+        Rarity::Legendary => "Legendary",
+    }
+}
+
+// Built-in abilities have fixed names; admin-defined ones are shown by id
+pub fn ability_name(ability_id: u16) -> String {
+    match ability_id {
+        CLUTCH_FACTOR_ID => "Clutch Factor".to_string(),
+        PERFECT_EXECUTION_ID => "Perfect Execution".to_string(),
+        SHOT_CALLER_ID => "Shot Caller".to_string(),
+        CREATOR_SIGNATURE_ID => "Creator Signature".to_string(),
+        id => format!("Ability #{}", id),
+    }
+}
+
+fn status_name(player: &PlayerAccount, now: i64) -> &'static str {
+    if player.retired {
+        "Retired"
+    } else if player.is_injured(now) {
+        "Injured"
+    } else if player.is_in_training() {
+        "In Training"
+    } else {
+        "Active"
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::Pubkey;
+use base64::Engine;
+use std::fs;
+use std::path::PathBuf;
+
+// Where raw account data comes from
+pub trait AccountSource {
+    // Ok(None) when the account doesn't exist
+    fn fetch(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, String>;
+}
+
+// Account dumps on disk, one `<address>.bin` file per account
+// (the format written by `solana account <address> --output-file`)
+pub struct DirectorySource {
+    pub root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AccountSource for DirectorySource {
+    fn fetch(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, String> {
+        let path = self.root.join(format!("{}.bin", address));
+        match fs::read(&path) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("failed to read {}: {}", path.display(), err)),
+        }
+    }
+}
+
+// Live accounts from a Solana JSON-RPC endpoint
+pub struct RpcSource {
+    pub url: String,
+}
+
+impl RpcSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl AccountSource for RpcSource {
+    fn fetch(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, String> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [address.to_string(), { "encoding": "base64", "commitment": "confirmed" }],
+        });
+        
+        let response: serde_json::Value = ureq::post(&self.url)
+            .send_json(request)
+            .map_err(|err| format!("rpc request failed: {}", err))?
+            .into_json()
+            .map_err(|err| format!("invalid rpc response: {}", err))?;
+        
+        if let Some(error) = response.get("error") {
+            return Err(format!("rpc error: {}", error));
+        }
+        
+        let value = &response["result"]["value"];
+        if value.is_null() {
+            return Ok(None);
+        }
+        
+        let encoded = value["data"][0]
+            .as_str()
+            .ok_or_else(|| "rpc response is missing account data".to_string())?;
+        
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map(Some)
+            .map_err(|err| format!("invalid account data: {}", err))
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountSerialize;
+use esports_manager_contract::ability::{CLUTCH_FACTOR_ID, CREATOR_SIGNATURE_ID};
+use esports_manager_contract::player::{
+    GameSpecificData, HeroProficiency, PlayerAccount, Position, Rarity, SpecialAbility,
+};
+use player_metadata::player_uri;
+
+pub const NOW: i64 = 1_700_000_000;
+
+// A mid-career rostered exclusive athlete
+pub fn fixture_player(mint: Pubkey) -> PlayerAccount {
+    PlayerAccount {
+        last_updated: NOW - 3600,
+        team: Some(Pubkey::new_unique()),
+        mechanical: 92,
+        game_knowledge: 95,
+        team_communication: 88,
+        adaptability: 90,
+        consistency: 87,
+        special_abilities: vec![
+            SpecialAbility::new(CLUTCH_FACTOR_ID, 3),
+            SpecialAbility::new(CREATOR_SIGNATURE_ID, 80),
+        ],
+        game_specific_data: GameSpecificData {
+            heroes: vec![HeroProficiency { hero_id: 7, mastery: 95, games_played: 40 }],
+        },
+        experience: 5400,
+        matches_played: 120,
+        wins: 85,
+        mvp_count: 30,
+        form: 80,
+        potential: 97,
+        rarity: Rarity::Legendary,
+        minted_rarity: Rarity::Legendary,
+        potential_min: 97,
+        potential_max: 97,
+        potential_commitment: Some([7; 32]),
+        potential_revealed: true,
+        creator: Some(Pubkey::new_unique()),
+        is_exclusive: true,
+        form_updated_at: NOW,
+        last_match_at: NOW - 86400,
+        metadata_revision: 2,
+        has_metadata: true,
+        ..PlayerAccount::new(
+            Pubkey::new_unique(),
+            mint,
+            "Faker".to_string(),
+            Position::Mid,
+            player_uri("https://5vs5.gg", &mint),
+            21,
+            NOW - 86400,
+        )
+    }
+}
+
+// Raw account data as stored on chain (discriminator + borsh)
+pub fn account_data(player: &PlayerAccount) -> Vec<u8> {
+    let mut data = Vec::new();
+    player.try_serialize(&mut data).unwrap();
+    data
+}
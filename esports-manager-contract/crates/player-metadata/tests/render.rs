@@ -0,0 +1,96 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{account_data, fixture_player, NOW};
+use player_metadata::render::{decode_player, render_player, RenderConfig};
+use serde_json::Value;
+
+fn attribute<'a>(json: &'a Value, trait_type: &str) -> Option<&'a Value> {
+    json["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|attribute| attribute["trait_type"] == trait_type)
+        .map(|attribute| &attribute["value"])
+}
+
+fn render_json(data: &[u8]) -> Value {
+    let player = decode_player(data).unwrap();
+    serde_json::to_value(render_player(&player, &RenderConfig::default(), NOW)).unwrap()
+}
+
+#[test]
+fn renders_metaplex_fields() {
+    let mint = Pubkey::new_unique();
+    let json = render_json(&account_data(&fixture_player(mint)));
+    
+    assert_eq!(json["name"], "Faker");
+    assert_eq!(json["symbol"], "5VS5");
+    assert_eq!(json["image"], format!("https://5vs5.gg/images/players/{}.png", mint));
+    assert_eq!(json["properties"]["files"][0]["type"], "image/png");
+    assert_eq!(json["properties"]["category"], "image");
+}
+
+#[test]
+fn renders_stats_record_and_abilities_as_attributes() {
+    let json = render_json(&account_data(&fixture_player(Pubkey::new_unique())));
+    
+    assert_eq!(attribute(&json, "Position").unwrap(), "Mid");
+    assert_eq!(attribute(&json, "Rarity").unwrap(), "Legendary");
+    assert_eq!(attribute(&json, "Mechanical").unwrap(), 92);
+    assert_eq!(attribute(&json, "Consistency").unwrap(), 87);
+    assert_eq!(attribute(&json, "Wins").unwrap(), 85);
+    assert_eq!(attribute(&json, "Losses").unwrap(), 35);
+    assert_eq!(attribute(&json, "MVP Awards").unwrap(), 30);
+    assert_eq!(attribute(&json, "Clutch Factor").unwrap(), 3);
+    assert_eq!(attribute(&json, "Creator Signature").unwrap(), 80);
+    assert_eq!(attribute(&json, "Comfort Pick").unwrap(), "Hero #7");
+    assert_eq!(attribute(&json, "Exclusive").unwrap(), "Yes");
+    assert_eq!(attribute(&json, "Status").unwrap(), "Active");
+    assert_eq!(attribute(&json, "Potential").unwrap(), 97);
+}
+
+#[test]
+fn hidden_potential_shows_only_the_range() {
+    let mut player = fixture_player(Pubkey::new_unique());
+    player.potential_revealed = false;
+    player.potential_min = 90;
+    player.potential_max = 100;
+    
+    let json = render_json(&account_data(&player));
+    assert_eq!(attribute(&json, "Potential").unwrap(), "90-100");
+}
+
+#[test]
+fn renders_team_and_creator() {
+    let mut player = fixture_player(Pubkey::new_unique());
+    let json = render_json(&account_data(&player));
+    assert_eq!(attribute(&json, "Team").unwrap(), player.team.unwrap().to_string().as_str());
+    assert_eq!(attribute(&json, "Creator").unwrap(), player.creator.unwrap().to_string().as_str());
+    
+    player.team = None;
+    player.creator = None;
+    player.is_exclusive = false;
+    let json = render_json(&account_data(&player));
+    assert_eq!(attribute(&json, "Team").unwrap(), "Free Agent");
+    assert!(attribute(&json, "Creator").is_none());
+    assert_eq!(attribute(&json, "Exclusive").unwrap(), "No");
+}
+
+#[test]
+fn status_reflects_injury_and_retirement() {
+    let mut player = fixture_player(Pubkey::new_unique());
+    player.injured_until = NOW + 3600;
+    assert_eq!(attribute(&render_json(&account_data(&player)), "Status").unwrap(), "Injured");
+    
+    player.retired = true;
+    assert_eq!(attribute(&render_json(&account_data(&player)), "Status").unwrap(), "Retired");
+}
+
+#[test]
+fn rejects_non_player_accounts() {
+    let mut data = account_data(&fixture_player(Pubkey::new_unique()));
+    data[0] ^= 0xff;
+    assert!(decode_player(&data).is_err());
+    assert!(decode_player(&[]).is_err());
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{account_data, fixture_player, NOW};
+use esports_manager_contract::utils::pda::find_player_address;
+use player_metadata::{handle_request, player_path, DirectorySource, RenderConfig};
+use std::fs;
+use std::path::PathBuf;
+
+// Fresh fixture directory holding one player account dump
+fn fixture_dir(name: &str, mint: Pubkey, stored_mint: Pubkey) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("player-metadata-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    
+    let (address, _) = find_player_address(&mint, &esports_manager_contract::ID);
+    fs::write(dir.join(format!("{}.bin", address)), account_data(&fixture_player(stored_mint))).unwrap();
+    dir
+}
+
+#[test]
+fn serves_metadata_at_uri_path() {
+    let mint = Pubkey::new_unique();
+    let source = DirectorySource::new(fixture_dir("serve", mint, mint));
+    
+    let response = handle_request(&format!("{}?rev=2", player_path(&mint)), &source, &RenderConfig::default(), NOW);
+    assert_eq!(response.status, 200);
+    
+    let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+    assert_eq!(json["name"], "Faker");
+}
+
+#[test]
+fn missing_player_is_not_found() {
+    let mint = Pubkey::new_unique();
+    let source = DirectorySource::new(fixture_dir("missing", mint, mint));
+    
+    let other = Pubkey::new_unique();
+    let response = handle_request(&format!("/player/{}.json", other), &source, &RenderConfig::default(), NOW);
+    assert_eq!(response.status, 404);
+}
+
+#[test]
+fn rejects_bad_paths_and_mints() {
+    let mint = Pubkey::new_unique();
+    let source = DirectorySource::new(fixture_dir("paths", mint, mint));
+    let config = RenderConfig::default();
+    
+    assert_eq!(handle_request("/team/abc", &source, &config, NOW).status, 404);
+    assert_eq!(handle_request("/player/not-a-key.json", &source, &config, NOW).status, 400);
+}
+
+#[test]
+fn rejects_dump_for_another_mint() {
+    let mint = Pubkey::new_unique();
+    let source = DirectorySource::new(fixture_dir("mismatch", mint, Pubkey::new_unique()));
+    
+    let response = handle_request(&format!("/player/{}.json", mint), &source, &RenderConfig::default(), NOW);
+    assert_eq!(response.status, 422);
+}
+
+#[test]
+fn ignores_players_hosted_elsewhere() {
+    let mint = Pubkey::new_unique();
+    let dir = fixture_dir("elsewhere", mint, mint);
+    
+    let mut player = fixture_player(mint);
+    player.uri = "https://arweave.net/faker.json".to_string();
+    let (address, _) = find_player_address(&mint, &esports_manager_contract::ID);
+    fs::write(dir.join(format!("{}.bin", address)), account_data(&player)).unwrap();
+    
+    let response = handle_request(&player_path(&mint), &DirectorySource::new(dir), &RenderConfig::default(), NOW);
+    assert_eq!(response.status, 404);
+}
@@ -0,0 +1,542 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+use crate::player::{PlayerAccount, Rarity, StatType};
+use crate::config::ProgramConfig;
+use crate::utils::current_season;
+use crate::metadata::sync_player_metadata_if_minted;
+
+// Ability ids granted by the program itself (see player::check_for_level_ups)
+pub const CLUTCH_FACTOR_ID: u16 = 1;
+pub const PERFECT_EXECUTION_ID: u16 = 2;
+pub const SHOT_CALLER_ID: u16 = 3;
+pub const CREATOR_SIGNATURE_ID: u16 = 4;
+
+// Largest stat bonus an ability gives at its max value
+pub const MAX_ABILITY_BONUS: u16 = 10;
+
+// Every ability, built-in or from the catalog, is granted at this level and upgraded from there
+pub const STARTING_ABILITY_LEVEL: u8 = 1;
+
+// Level cap of the built-in abilities (one stat point per level)
+pub const BUILTIN_ABILITY_MAX_LEVEL: u8 = 10;
+
+// Upgrade costs per level gained
+pub const UPGRADE_EXPERIENCE_PER_LEVEL: u32 = 100;
+pub const UPGRADE_LAMPORTS_PER_LEVEL: u64 = 10_000_000; // 0.01 SOL
+
+// Share of the invested cost returned when an ability is respecced
+pub const RESPEC_REFUND_PERCENT: u64 = 50;
+
+// Ability Definition Structure (admin-managed catalog entry)
+#[account]
+pub struct AbilityDefinition {
+    pub authority: Pubkey,
+    pub ability_id: u16,
+    pub name: String,
+    pub effect_type: AbilityEffectType,
+    pub condition: EffectCondition,
+    pub max_value: u8,
+    pub required_rarity: Rarity,
+    pub unlock_conditions: UnlockConditions,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl AbilityDefinition {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // authority pubkey
+        2 + // ability_id
+        36 + // name (max 32 chars)
+        2 + // effect_type
+        1 + // condition
+        1 + // max_value
+        1 + // required_rarity
+        UnlockConditions::LEN + // unlock_conditions
+        8; // created_at
+    
+    // Check whether a player meets the unlock conditions (rarity is checked separately)
+    pub fn is_unlocked_for(&self, player: &PlayerAccount) -> bool {
+        let conditions = &self.unlock_conditions;
+        
+        player.experience >= conditions.min_experience &&
+            player.matches_played >= conditions.min_matches_played &&
+            player.wins >= conditions.min_wins &&
+            player.mvp_count >= conditions.min_mvp_count &&
+            conditions.stat_requirement.iter()
+                .all(|r| player.stat(&r.stat) >= r.min_value)
+    }
+    
+    pub fn effect(&self) -> AbilityEffect {
+        AbilityEffect {
+            effect_type: self.effect_type.clone(),
+            condition: self.condition,
+            max_value: self.max_value,
+        }
+    }
+}
+
+// What an ability modifies
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum AbilityEffectType {
+    StatBoost(StatType),   // Boosts one of the player's own stats
+    TeamBoost(StatType),   // Boosts a stat across the whole lineup
+    FormBoost,             // Boosts the player's form
+}
+
+// When an ability's effect is active
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum EffectCondition {
+    Always,
+    LateGame,
+    TrailingOnScore,
+    LeadingOnScore,
+}
+
+impl EffectCondition {
+    pub fn applies(&self, situation: &MatchSituation) -> bool {
+        match self {
+            EffectCondition::Always => true,
+            EffectCondition::LateGame => situation.late_game,
+            EffectCondition::TrailingOnScore => situation.team_score < situation.opponent_score,
+            EffectCondition::LeadingOnScore => situation.team_score > situation.opponent_score,
+        }
+    }
+}
+
+// Game state that match resolution evaluates abilities against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct MatchSituation {
+    pub late_game: bool,
+    pub team_score: u8,
+    pub opponent_score: u8,
+}
+
+// Resolved effect of an ability, from the catalog or the built-in table
+#[derive(Clone)]
+pub struct AbilityEffect {
+    pub effect_type: AbilityEffectType,
+    pub condition: EffectCondition,
+    pub max_value: u8,
+}
+
+impl AbilityEffect {
+    // Bonus scales linearly with level up to MAX_ABILITY_BONUS at max_value
+    pub fn bonus(&self, level: u8) -> u8 {
+        let level = std::cmp::min(level, self.max_value) as u16;
+        (level * MAX_ABILITY_BONUS / std::cmp::max(1, self.max_value as u16)) as u8
+    }
+}
+
+// Stat bonuses an ability set grants in a given situation, indexed like StatType
+#[derive(Clone, Default)]
+pub struct AbilityModifiers {
+    pub player: [u8; 5],
+    pub team: [u8; 5],
+    pub form: u8,
+}
+
+// How an ability upgrade is paid for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum UpgradePayment {
+    Experience,
+    Lamports,
+}
+
+// Requirements a player must meet before an ability can be granted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UnlockConditions {
+    pub min_experience: u32,
+    pub min_matches_played: u32,
+    pub min_wins: u32,
+    pub min_mvp_count: u32,
+    pub stat_requirement: Option<StatRequirement>,
+}
+
+impl UnlockConditions {
+    pub const LEN: usize =
+        4 + // min_experience
+        4 + // min_matches_played
+        4 + // min_wins
+        4 + // min_mvp_count
+        3; // stat_requirement (Option<StatRequirement>)
+}
+
+// Minimum value for a single stat
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StatRequirement {
+    pub stat: StatType,
+    pub min_value: u8,
+}
+
+// Catalog fields set when an ability definition is created or updated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AbilityDefinitionParams {
+    pub name: String,
+    pub effect_type: AbilityEffectType,
+    pub condition: EffectCondition,
+    pub max_value: u8,
+    pub required_rarity: Rarity,
+    pub unlock_conditions: UnlockConditions,
+}
+
+// Context for creating an ability definition
+#[derive(Accounts)]
+#[instruction(ability_id: u16)]
+pub struct CreateAbilityDefinition<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AbilityDefinition::LEN,
+        seeds = [b"ability", ability_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ability_definition: Account<'info, AbilityDefinition>,
+    
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Context for updating an ability definition
+#[derive(Accounts)]
+pub struct UpdateAbilityDefinition<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"ability", ability_definition.ability_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub ability_definition: Account<'info, AbilityDefinition>,
+}
+
+// Context for upgrading one of a player's abilities
+#[derive(Accounts)]
+pub struct UpgradeAbility<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    // Catalog entry for the ability (not needed for built-in abilities)
+    #[account(
+        seeds = [b"ability", ability_definition.ability_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ability_definition: Option<Account<'info, AbilityDefinition>>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Metaplex metadata for the player mint, verified in sync_player_metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+}
+
+// Context for respeccing a player's abilities
+#[derive(Accounts)]
+pub struct RespecAbilities<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Protocol treasury PDA, signs lamport refunds
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Register a new ability in the catalog
+pub fn create_ability_definition(
+    ctx: Context<CreateAbilityDefinition>,
+    ability_id: u16,
+    params: AbilityDefinitionParams,
+) -> Result<()> {
+    // Built-in ids would be shadowed by the built-in table
+    require!(builtin_effect(ability_id).is_none(), ErrorCode::ReservedAbilityId);
+    
+    let ability_definition = &mut ctx.accounts.ability_definition;
+    let clock = Clock::get()?;
+    
+    ability_definition.authority = ctx.accounts.authority.key();
+    ability_definition.ability_id = ability_id;
+    ability_definition.created_at = clock.unix_timestamp;
+    
+    set_ability_params(ability_definition, params)
+}
+
+// Update an existing catalog entry (the id is fixed by the PDA)
+pub fn update_ability_definition(
+    ctx: Context<UpdateAbilityDefinition>,
+    params: AbilityDefinitionParams,
+) -> Result<()> {
+    set_ability_params(&mut ctx.accounts.ability_definition, params)
+}
+
+// Validate and store the catalog fields of an ability definition
+fn set_ability_params(ability_definition: &mut AbilityDefinition, params: AbilityDefinitionParams) -> Result<()> {
+    require!(
+        params.max_value > 0 && params.name.len() <= 32,
+        ErrorCode::InvalidAbilityDefinition
+    );
+    
+    ability_definition.name = params.name;
+    ability_definition.effect_type = params.effect_type;
+    ability_definition.condition = params.condition;
+    ability_definition.max_value = params.max_value;
+    ability_definition.required_rarity = params.required_rarity;
+    ability_definition.unlock_conditions = params.unlock_conditions;
+    
+    Ok(())
+}
+
+// Effects of the abilities granted by the program itself
+pub fn builtin_effect(ability_id: u16) -> Option<AbilityEffect> {
+    let (effect_type, condition) = match ability_id {
+        CLUTCH_FACTOR_ID => (
+            AbilityEffectType::StatBoost(StatType::Consistency),
+            EffectCondition::TrailingOnScore,
+        ),
+        PERFECT_EXECUTION_ID => (
+            AbilityEffectType::StatBoost(StatType::Mechanical),
+            EffectCondition::LateGame,
+        ),
+        SHOT_CALLER_ID => (
+            AbilityEffectType::TeamBoost(StatType::TeamCommunication),
+            EffectCondition::Always,
+        ),
+        CREATOR_SIGNATURE_ID => (
+            AbilityEffectType::FormBoost,
+            EffectCondition::Always,
+        ),
+        _ => return None,
+    };
+    
+    Some(AbilityEffect {
+        effect_type,
+        condition,
+        max_value: BUILTIN_ABILITY_MAX_LEVEL,
+    })
+}
+
+// Look up an ability's effect, preferring the built-in table over catalog entries
+pub fn find_ability_effect(ability_id: u16, definitions: &[AbilityDefinition]) -> Option<AbilityEffect> {
+    builtin_effect(ability_id).or_else(|| {
+        definitions.iter()
+            .find(|d| d.ability_id == ability_id)
+            .map(|d| d.effect())
+    })
+}
+
+// Sum up the bonuses of a player's abilities that are active in this situation
+// Catalog abilities without a matching definition in `definitions` are skipped
+pub fn calculate_ability_modifiers(
+    player: &PlayerAccount,
+    situation: &MatchSituation,
+    definitions: &[AbilityDefinition],
+) -> AbilityModifiers {
+    let mut modifiers = AbilityModifiers::default();
+    
+    for ability in player.special_abilities.iter() {
+        let effect = match find_ability_effect(ability.ability_id, definitions) {
+            Some(effect) => effect,
+            None => continue,
+        };
+        
+        if !effect.condition.applies(situation) {
+            continue;
+        }
+        
+        let bonus = effect.bonus(ability.level);
+        match &effect.effect_type {
+            AbilityEffectType::StatBoost(stat) => {
+                modifiers.player[stat.index()] = modifiers.player[stat.index()].saturating_add(bonus);
+            }
+            AbilityEffectType::TeamBoost(stat) => {
+                modifiers.team[stat.index()] = modifiers.team[stat.index()].saturating_add(bonus);
+            }
+            AbilityEffectType::FormBoost => {
+                modifiers.form = modifiers.form.saturating_add(bonus);
+            }
+        }
+    }
+    
+    modifiers
+}
+
+// Raise the level of an ability the player already holds
+pub fn upgrade_ability(
+    ctx: Context<UpgradeAbility>,
+    ability_id: u16,
+    levels: u8,
+    payment: UpgradePayment,
+) -> Result<()> {
+    require!(levels > 0, ErrorCode::InvalidUpgradeAmount);
+    
+    let player_account = &mut ctx.accounts.player_account;
+    
+    // Resolve the level cap from the built-in table or the catalog entry
+    let definitions: Vec<AbilityDefinition> = ctx.accounts.ability_definition
+        .iter()
+        .map(|d| d.clone().into_inner())
+        .collect();
+    let effect = find_ability_effect(ability_id, &definitions)
+        .ok_or(ErrorCode::AbilityNotFound)?;
+    
+    let index = player_account.special_abilities
+        .iter()
+        .position(|a| a.ability_id == ability_id)
+        .ok_or(ErrorCode::AbilityNotFound)?;
+    
+    let new_level = player_account.special_abilities[index].level.saturating_add(levels);
+    require!(new_level <= effect.max_value, ErrorCode::AbilityMaxLevel);
+    
+    match payment {
+        UpgradePayment::Experience => {
+            let cost = UPGRADE_EXPERIENCE_PER_LEVEL * levels as u32;
+            require!(player_account.experience >= cost, ErrorCode::InsufficientExperience);
+            
+            player_account.experience -= cost;
+            let ability = &mut player_account.special_abilities[index];
+            ability.invested_experience = ability.invested_experience.saturating_add(cost);
+        }
+        UpgradePayment::Lamports => {
+            let cost = UPGRADE_LAMPORTS_PER_LEVEL * levels as u64;
+            
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                cost,
+            )?;
+            
+            let ability = &mut player_account.special_abilities[index];
+            ability.invested_lamports = ability.invested_lamports.saturating_add(cost);
+        }
+    }
+    
+    player_account.special_abilities[index].level = new_level;
+    player_account.last_updated = Clock::get()?.unix_timestamp;
+    player_account.metadata_revision += 1;
+    
+    sync_player_metadata_if_minted(
+        &ctx.accounts.player_account,
+        ctx.bumps.player_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+    )
+}
+
+// Remove catalog abilities and refund part of what was invested in them (once per season)
+pub fn respec_abilities(
+    ctx: Context<RespecAbilities>,
+    ability_ids: Vec<u16>,
+) -> Result<()> {
+    require!(!ability_ids.is_empty(), ErrorCode::AbilityNotFound);
+    
+    let player_account = &mut ctx.accounts.player_account;
+    let clock = Clock::get()?;
+    let season = current_season(clock.unix_timestamp);
+    
+    require!(
+        player_account.last_respec_season != Some(season),
+        ErrorCode::RespecCooldown
+    );
+    
+    let mut refund_experience: u64 = 0;
+    let mut refund_lamports: u64 = 0;
+    
+    for ability_id in ability_ids.iter() {
+        // Built-in abilities are re-granted by their achievements, so they can't be respecced
+        require!(builtin_effect(*ability_id).is_none(), ErrorCode::BuiltinAbilityRespec);
+        
+        let index = player_account.special_abilities
+            .iter()
+            .position(|a| a.ability_id == *ability_id)
+            .ok_or(ErrorCode::AbilityNotFound)?;
+        
+        let ability = player_account.special_abilities.remove(index);
+        refund_experience += ability.invested_experience as u64 * RESPEC_REFUND_PERCENT / 100;
+        refund_lamports += ability.invested_lamports * RESPEC_REFUND_PERCENT / 100;
+    }
+    
+    player_account.experience = player_account.experience
+        .saturating_add(std::cmp::min(refund_experience, u32::MAX as u64) as u32);
+    player_account.last_respec_season = Some(season);
+    player_account.last_updated = clock.unix_timestamp;
+    
+    if refund_lamports > 0 {
+        let bump = ctx.bumps.treasury;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", &[bump]]];
+        
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.owner.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_lamports,
+        )?;
+    }
+    
+    msg!("Respec refunded {} experience and {} lamports", refund_experience, refund_lamports);
+    
+    Ok(())
+}
@@ -0,0 +1,538 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::errors::ErrorCode;
+use crate::creator::CreatorAccount;
+use crate::marketplace::{creator_royalty, pay_from_wallet, release_escrow, split_sale};
+use crate::player::PlayerAccount;
+use crate::utils::move_lamports;
+
+// A bid this close to the end pushes the end back to give others a chance to respond
+pub const ANTI_SNIPE_WINDOW: i64 = 300; // 5 minutes
+
+// Shortest and longest auctions
+pub const MIN_AUCTION_DURATION: i64 = 3600; // 1 hour
+pub const MAX_AUCTION_DURATION: i64 = 7 * 86400; // 7 days
+
+// Auction formats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AuctionKind {
+    English, // Ascending bids, highest bid wins at the end
+    Dutch,   // Price falls linearly, first buyer wins
+}
+
+// Player Auction Structure (the NFT sits in an escrow token account owned by this PDA,
+// and the highest English bid is held in the PDA itself)
+#[account]
+pub struct PlayerAuction {
+    pub seller: Pubkey,
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub kind: AuctionKind,
+    pub start_price: u64,   // English: reserve price; Dutch: opening price
+    pub end_price: u64,     // Dutch: floor price; unused for English
+    pub min_increment: u64, // English: smallest raise over the highest bid
+    pub start_time: i64,
+    pub end_time: i64,
+    pub highest_bid: u64,
+    pub highest_bidder: Option<Pubkey>,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PlayerAuction {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // seller pubkey
+        32 + // player pubkey
+        32 + // mint pubkey
+        1 + // kind
+        8 + // start_price
+        8 + // end_price
+        8 + // min_increment
+        8 + // start_time
+        8 + // end_time
+        8 + // highest_bid
+        33 + // highest_bidder (Option<Pubkey>)
+        8; // created_at
+    
+    // Dutch price at a moment, decaying linearly from start_price to end_price
+    pub fn current_price(&self, now: i64) -> u64 {
+        if now >= self.end_time {
+            return self.end_price;
+        }
+        
+        let elapsed = std::cmp::max(0, now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let drop = (self.start_price - self.end_price) as u128 * elapsed / duration;
+        
+        self.start_price - drop as u64
+    }
+    
+    // Smallest bid the auction will currently accept
+    pub fn minimum_bid(&self) -> u64 {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid.saturating_add(self.min_increment),
+            None => self.start_price,
+        }
+    }
+}
+
+// Context for putting a player up for auction
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == seller.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(address = player_account.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = PlayerAuction::LEN,
+        seeds = [b"auction", player_account.key().as_ref()],
+        bump
+    )]
+    pub player_auction: Account<'info, PlayerAuction>,
+    
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"auction_escrow", player_auction.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = player_auction
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for bidding in an English auction
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction", player_auction.player.as_ref()],
+        bump,
+        constraint = player_auction.kind == AuctionKind::English @ ErrorCode::InvalidAuction,
+        constraint = player_auction.seller != bidder.key() @ ErrorCode::InvalidSale
+    )]
+    pub player_auction: Account<'info, PlayerAuction>,
+    
+    /// CHECK: Outbid bidder, refunded in full; must match the current highest bidder
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for buying a Dutch auction at the current price
+#[derive(Accounts)]
+pub struct BuyDutchAuction<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"auction", player_account.key().as_ref()],
+        bump,
+        has_one = seller @ ErrorCode::UnauthorizedAccess,
+        constraint = player_auction.kind == AuctionKind::Dutch @ ErrorCode::InvalidAuction,
+        constraint = player_auction.seller != buyer.key() @ ErrorCode::InvalidSale
+    )]
+    pub player_auction: Account<'info, PlayerAuction>,
+    
+    // Escrowed players can't be rostered or rented, so an auctioned player always sells
+    #[account(
+        mut,
+        address = player_auction.player,
+        constraint = player_account.owner == player_auction.seller @ ErrorCode::UnauthorizedAccess
+    )]
+    pub player_account: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", player_auction.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = player_auction.mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Seller, receives the proceeds and the auction rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Required when the player was minted by a creator
+    pub creator_account: Option<Account<'info, CreatorAccount>>,
+    
+    /// CHECK: Creator authority, receives the royalty; checked against creator_account
+    #[account(mut)]
+    pub creator_authority: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for settling a finished English auction (permissionless)
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"auction", player_account.key().as_ref()],
+        bump,
+        has_one = seller @ ErrorCode::UnauthorizedAccess,
+        constraint = player_auction.kind == AuctionKind::English @ ErrorCode::InvalidAuction
+    )]
+    pub player_auction: Account<'info, PlayerAuction>,
+    
+    #[account(
+        mut,
+        address = player_auction.player
+    )]
+    pub player_account: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", player_auction.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    
+    // Winner's token account, or the seller's if nobody bid
+    #[account(
+        mut,
+        token::mint = player_auction.mint
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Seller, receives the proceeds and the auction rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Required when the player was minted by a creator
+    pub creator_account: Option<Account<'info, CreatorAccount>>,
+    
+    /// CHECK: Creator authority, receives the royalty; checked against creator_account
+    #[account(mut)]
+    pub creator_authority: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Context for the seller calling off an auction nobody has bid on
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"auction", player_auction.player.as_ref()],
+        bump,
+        has_one = seller @ ErrorCode::UnauthorizedAccess,
+        constraint = player_auction.highest_bidder.is_none() @ ErrorCode::AuctionHasBids
+    )]
+    pub player_auction: Account<'info, PlayerAuction>,
+    
+    #[account(
+        mut,
+        address = player_auction.player
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", player_auction.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = player_auction.mint,
+        token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Put a player up for auction; the NFT moves into escrow until settled or canceled
+pub fn create_auction(
+    ctx: Context<CreateAuction>,
+    kind: AuctionKind,
+    start_price: u64,
+    end_price: u64,
+    min_increment: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(
+        (MIN_AUCTION_DURATION..=MAX_AUCTION_DURATION).contains(&duration),
+        ErrorCode::InvalidAuction
+    );
+    match kind {
+        AuctionKind::English => require!(start_price > 0 && min_increment > 0, ErrorCode::InvalidAuction),
+        AuctionKind::Dutch => require!(start_price > end_price && end_price > 0, ErrorCode::InvalidAuction),
+    }
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    let player_auction = &mut ctx.accounts.player_auction;
+    let clock = Clock::get()?;
+    
+    player_auction.seller = ctx.accounts.seller.key();
+    player_auction.player = ctx.accounts.player_account.key();
+    player_auction.mint = ctx.accounts.player_account.mint;
+    player_auction.kind = kind;
+    player_auction.start_price = start_price;
+    player_auction.end_price = if kind == AuctionKind::Dutch { end_price } else { 0 };
+    player_auction.min_increment = if kind == AuctionKind::English { min_increment } else { 0 };
+    player_auction.start_time = clock.unix_timestamp;
+    player_auction.end_time = clock.unix_timestamp + duration;
+    player_auction.highest_bid = 0;
+    player_auction.highest_bidder = None;
+    player_auction.created_at = clock.unix_timestamp;
+    ctx.accounts.player_account.escrowed = true;
+    
+    msg!("{} up for auction until {}", ctx.accounts.player_account.name, player_auction.end_time);
+    
+    Ok(())
+}
+
+// Bid in an English auction; the bid is escrowed and the previous highest bidder refunded
+pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let player_auction = &ctx.accounts.player_auction;
+    
+    require!(now < player_auction.end_time, ErrorCode::AuctionEnded);
+    require!(amount >= player_auction.minimum_bid(), ErrorCode::BidTooLow);
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: player_auction.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    // Refund the bidder who was just outbid
+    if let Some(previous) = player_auction.highest_bidder {
+        let previous_bidder = ctx.accounts.previous_bidder.as_ref().ok_or(ErrorCode::InvalidBidder)?;
+        require_keys_eq!(previous_bidder.key(), previous, ErrorCode::InvalidBidder);
+        
+        move_lamports(
+            &player_auction.to_account_info(),
+            &previous_bidder.to_account_info(),
+            player_auction.highest_bid,
+        )?;
+    }
+    
+    let player_auction = &mut ctx.accounts.player_auction;
+    player_auction.highest_bid = amount;
+    player_auction.highest_bidder = Some(ctx.accounts.bidder.key());
+    
+    // Anti-snipe: late bids extend the auction
+    if player_auction.end_time - now < ANTI_SNIPE_WINDOW {
+        player_auction.end_time = now + ANTI_SNIPE_WINDOW;
+    }
+    
+    msg!("New high bid of {} lamports", amount);
+    
+    Ok(())
+}
+
+// Buy a Dutch auction at its current price
+pub fn buy_dutch_auction(ctx: Context<BuyDutchAuction>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_auction = &ctx.accounts.player_auction;
+    
+    require!(clock.unix_timestamp < player_auction.end_time, ErrorCode::AuctionEnded);
+    
+    let price = player_auction.current_price(clock.unix_timestamp);
+    let royalty = creator_royalty(
+        &ctx.accounts.player_account,
+        ctx.accounts.creator_account.as_ref(),
+        ctx.accounts.creator_authority.as_ref(),
+    )?;
+    let split = split_sale(price, royalty.as_ref().map_or(0, |(bps, _)| *bps));
+    
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    pay_from_wallet(&system_program, &buyer, &ctx.accounts.seller.to_account_info(), split.seller_proceeds)?;
+    pay_from_wallet(&system_program, &buyer, &ctx.accounts.treasury.to_account_info(), split.protocol_fee)?;
+    if let Some((_, creator_authority)) = royalty {
+        pay_from_wallet(&system_program, &buyer, &creator_authority, split.royalty)?;
+    }
+    
+    release_auction_escrow(
+        &ctx.accounts.player_auction,
+        ctx.bumps.player_auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.buyer_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let player_account = &mut ctx.accounts.player_account;
+    player_account.owner = ctx.accounts.buyer.key();
+    player_account.escrowed = false;
+    
+    msg!("{} sold at auction for {} lamports", player_account.name, price);
+    
+    Ok(())
+}
+
+// Settle a finished English auction. The winner gets the NFT and the seller the proceeds;
+// if there was no bid the NFT goes back to the seller
+pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_auction = &ctx.accounts.player_auction;
+    
+    require!(clock.unix_timestamp >= player_auction.end_time, ErrorCode::AuctionNotEnded);
+    
+    let player_account = &ctx.accounts.player_account;
+    let winner = player_auction.highest_bidder;
+    let recipient = winner.unwrap_or(player_auction.seller);
+    
+    require_keys_eq!(ctx.accounts.destination_token_account.owner, recipient, ErrorCode::InvalidBidder);
+    
+    let auction_info = player_auction.to_account_info();
+    let amount = player_auction.highest_bid;
+    
+    if winner.is_some() {
+        let royalty = creator_royalty(
+            player_account,
+            ctx.accounts.creator_account.as_ref(),
+            ctx.accounts.creator_authority.as_ref(),
+        )?;
+        let split = split_sale(amount, royalty.as_ref().map_or(0, |(bps, _)| *bps));
+        
+        move_lamports(&auction_info, &ctx.accounts.seller.to_account_info(), split.seller_proceeds)?;
+        move_lamports(&auction_info, &ctx.accounts.treasury.to_account_info(), split.protocol_fee)?;
+        if let Some((_, creator_authority)) = royalty {
+            move_lamports(&auction_info, &creator_authority, split.royalty)?;
+        }
+    }
+    
+    release_auction_escrow(
+        &ctx.accounts.player_auction,
+        ctx.bumps.player_auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.destination_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let player_account = &mut ctx.accounts.player_account;
+    player_account.escrowed = false;
+    
+    if let Some(winner) = winner {
+        player_account.owner = winner;
+        
+        msg!("{} sold at auction for {} lamports", player_account.name, amount);
+    }
+    
+    Ok(())
+}
+
+// Call off an auction before any bid or purchase, returning the NFT
+pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+    ctx.accounts.player_account.escrowed = false;
+    
+    release_auction_escrow(
+        &ctx.accounts.player_auction,
+        ctx.bumps.player_auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.seller_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )
+}
+
+// Helper to release an auction's escrow, signing as the auction PDA
+fn release_auction_escrow<'info>(
+    player_auction: &Account<'info, PlayerAuction>,
+    auction_bump: u8,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        b"auction".as_ref(),
+        player_auction.player.as_ref(),
+        &[auction_bump],
+    ];
+    
+    release_escrow(
+        &player_auction.to_account_info(),
+        &[&seeds[..]],
+        escrow_token_account,
+        destination,
+        rent_receiver,
+        token_program,
+    )
+}
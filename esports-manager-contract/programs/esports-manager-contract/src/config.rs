@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::program::EsportsManager;
+
+// Program Config Structure (singleton holding the protocol admin)
+#[account]
+pub struct ProgramConfig {
+    pub authority: Pubkey,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl ProgramConfig {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // authority pubkey
+        8; // created_at
+}
+
+// Context for creating the program config (only the upgrade authority can do this)
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::UnauthorizedAccess
+    )]
+    pub program: Program<'info, EsportsManager>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ ErrorCode::UnauthorizedAccess
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Create the program config with the protocol admin
+pub fn initialize_config(ctx: Context<InitializeConfig>, authority: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let clock = Clock::get()?;
+    
+    config.authority = authority;
+    config.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
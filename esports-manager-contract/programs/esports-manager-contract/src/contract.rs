@@ -0,0 +1,351 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+use crate::player::{PlayerAccount, MORALE_PAID, MORALE_UNPAID};
+use crate::team::{refresh_roster_statistics, remove_from_roster, TeamAccount};
+use crate::utils::safe_update_stat;
+
+// Salaries are paid once per period
+pub const PAYROLL_PERIOD: i64 = 7 * 86400;
+
+// Longest contract that can be signed (about a year)
+pub const MAX_CONTRACT_PERIODS: u16 = 52;
+
+// Unpaid salary periods after which the player's owner can walk away from the contract
+// (once the contract has run out, any unpaid period is enough)
+pub const UNPAID_TERMINATION_PERIODS: u16 = 2;
+
+// Player Contract Structure (one per team and player)
+#[account]
+pub struct PlayerContract {
+    pub team: Pubkey,
+    pub player: Pubkey,
+    pub player_mint: Pubkey,
+    pub salary_per_period: u64,
+    pub periods: u16,
+    pub periods_paid: u16,
+    pub started_at: i64,
+    pub expires_at: i64,
+}
+
+// Fixed size for account allocation
+impl PlayerContract {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // team pubkey
+        32 + // player pubkey
+        32 + // player_mint pubkey
+        8 + // salary_per_period
+        2 + // periods
+        2 + // periods_paid
+        8 + // started_at
+        8; // expires_at
+    
+    // Salary periods that have elapsed but not been paid yet
+    pub fn periods_due(&self, now: i64) -> u16 {
+        let elapsed = std::cmp::max(0, std::cmp::min(now, self.expires_at) - self.started_at);
+        let periods_elapsed = std::cmp::min(elapsed / PAYROLL_PERIOD, self.periods as i64) as u16;
+        periods_elapsed.saturating_sub(self.periods_paid)
+    }
+    
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+// Set up a new contract when a player joins a team
+pub fn sign_player_contract(
+    contract: &mut PlayerContract,
+    team: Pubkey,
+    player: &PlayerAccount,
+    player_key: Pubkey,
+    salary_per_period: u64,
+    periods: u16,
+    now: i64,
+) -> Result<()> {
+    require!(
+        periods > 0 && periods <= MAX_CONTRACT_PERIODS,
+        ErrorCode::InvalidContractTerms
+    );
+    
+    contract.team = team;
+    contract.player = player_key;
+    contract.player_mint = player.mint;
+    contract.salary_per_period = salary_per_period;
+    contract.periods = periods;
+    contract.periods_paid = 0;
+    contract.started_at = now;
+    contract.expires_at = now + periods as i64 * PAYROLL_PERIOD;
+    
+    Ok(())
+}
+
+// Context for depositing into a team treasury
+#[derive(Accounts)]
+pub struct FundTeamTreasury<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub team_account: Account<'info, TeamAccount>,
+    
+    /// CHECK: Team treasury PDA, a plain system account holding the payroll budget
+    #[account(
+        mut,
+        seeds = [b"team_treasury", team_account.key().as_ref()],
+        bump
+    )]
+    pub team_treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the owner withdrawing from a team treasury
+#[derive(Accounts)]
+pub struct WithdrawTeamTreasury<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        constraint = team_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    /// CHECK: Team treasury PDA
+    #[account(
+        mut,
+        seeds = [b"team_treasury", team_account.key().as_ref()],
+        bump
+    )]
+    pub team_treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the permissionless payroll crank
+#[derive(Accounts)]
+pub struct RunPayroll<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"contract", player_contract.team.as_ref(), player_contract.player.as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+    
+    #[account(
+        mut,
+        address = player_contract.player
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Team treasury PDA paying the salary
+    #[account(
+        mut,
+        seeds = [b"team_treasury", player_contract.team.as_ref()],
+        bump
+    )]
+    pub team_treasury: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the salary, must be the player's owner
+    #[account(
+        mut,
+        address = player_account.owner
+    )]
+    pub player_owner: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for ending an expired contract
+#[derive(Accounts)]
+pub struct ExpireContract<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = team_owner,
+        seeds = [b"contract", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+    
+    #[account(mut)]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        constraint = player_account.team == Some(team_account.key()) @ ErrorCode::PlayerNotOnTeam
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Receives the contract rent back
+    #[account(
+        mut,
+        address = team_account.owner
+    )]
+    pub team_owner: UncheckedAccount<'info>,
+}
+
+// Context for the player's owner ending a contract the team has stopped paying
+#[derive(Accounts)]
+pub struct TerminateUnpaidContract<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = team_owner,
+        seeds = [b"contract", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+    
+    #[account(mut)]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team == Some(team_account.key()) @ ErrorCode::PlayerNotOnTeam
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Receives the contract rent back
+    #[account(
+        mut,
+        address = team_account.owner
+    )]
+    pub team_owner: UncheckedAccount<'info>,
+}
+
+// Add lamports to a team's payroll budget (anyone can fund a team)
+pub fn fund_team_treasury(ctx: Context<FundTeamTreasury>, amount: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.team_treasury.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+// Take lamports back out of a team's payroll budget
+pub fn withdraw_team_treasury(ctx: Context<WithdrawTeamTreasury>, amount: u64) -> Result<()> {
+    let team_key = ctx.accounts.team_account.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"team_treasury", team_key.as_ref(), &[ctx.bumps.team_treasury]]];
+    
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.team_treasury.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+// Pay every salary period that has come due
+pub fn run_payroll(ctx: Context<RunPayroll>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_contract = &mut ctx.accounts.player_contract;
+    
+    let periods_due = player_contract.periods_due(clock.unix_timestamp);
+    require!(periods_due > 0, ErrorCode::NoSalaryDue);
+    
+    let amount = player_contract.salary_per_period
+        .checked_mul(periods_due as u64)
+        .ok_or(ErrorCode::InvalidContractTerms)?;
+    require!(
+        ctx.accounts.team_treasury.lamports() >= amount,
+        ErrorCode::InsufficientTeamFunds
+    );
+    
+    let team_key = player_contract.team;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"team_treasury", team_key.as_ref(), &[ctx.bumps.team_treasury]]];
+    
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.team_treasury.to_account_info(),
+                to: ctx.accounts.player_owner.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    
+    player_contract.periods_paid += periods_due;
+    
+    // Only the latest period is on time; every earlier one was paid late
+    let late_periods = std::cmp::min(periods_due - 1, 20) as i8;
+    let morale_change = if late_periods == 0 { MORALE_PAID } else { MORALE_UNPAID * late_periods };
+    safe_update_stat(&mut ctx.accounts.player_account.morale, morale_change);
+    
+    msg!("Paid {} lamports for {} salary periods", amount, periods_due);
+    
+    Ok(())
+}
+
+// Release the player to free agency once the contract has run out and been paid in full
+// (pass the rest of the team's roster as remaining accounts)
+pub fn expire_contract(ctx: Context<ExpireContract>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_contract = &ctx.accounts.player_contract;
+    
+    require!(player_contract.is_expired(clock.unix_timestamp), ErrorCode::ContractActive);
+    require!(
+        player_contract.periods_paid == player_contract.periods,
+        ErrorCode::SalaryOutstanding
+    );
+    
+    let team_account = &mut ctx.accounts.team_account;
+    let player_account = &mut ctx.accounts.player_account;
+    
+    remove_from_roster(team_account, &player_account.mint)?;
+    player_account.team = None;
+    team_account.last_updated = clock.unix_timestamp;
+    
+    let team_key = team_account.key();
+    refresh_roster_statistics(team_account, team_key, ctx.remaining_accounts, None)?;
+    
+    msg!("{} is now a free agent", player_account.name);
+    
+    Ok(())
+}
+
+// Release the player to free agency when the team has fallen behind on salary
+// (pass the rest of the team's roster as remaining accounts)
+pub fn terminate_unpaid_contract(ctx: Context<TerminateUnpaidContract>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_contract = &ctx.accounts.player_contract;
+    let periods_due = player_contract.periods_due(clock.unix_timestamp);
+    
+    // An expired contract stops accruing periods, so arrears of any size end it
+    let in_arrears = if player_contract.is_expired(clock.unix_timestamp) {
+        periods_due > 0
+    } else {
+        periods_due >= UNPAID_TERMINATION_PERIODS
+    };
+    require!(in_arrears, ErrorCode::ContractInGoodStanding);
+    
+    let team_account = &mut ctx.accounts.team_account;
+    let player_account = &mut ctx.accounts.player_account;
+    
+    remove_from_roster(team_account, &player_account.mint)?;
+    player_account.team = None;
+    team_account.last_updated = clock.unix_timestamp;
+    
+    let team_key = team_account.key();
+    refresh_roster_statistics(team_account, team_key, ctx.remaining_accounts, None)?;
+    
+    msg!("{} walked out over unpaid salary and is now a free agent", player_account.name);
+    
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 use crate::errors::ErrorCode;
-use crate::player::{PlayerAccount, PlayerStats, Rarity, SpecialAbility};
+use crate::player::{
+    potential_window, PlayerAccount, PlayerStats, Position, Rarity, SpecialAbility,
+};
+use crate::ability::CREATOR_SIGNATURE_ID;
 use crate::utils::safe_update_stat;
 use crate::utils::is_admin;
 
@@ -15,6 +18,7 @@ pub struct CreatorAccount {
     pub collections_created: Vec<Pubkey>,
     pub total_athletes_created: u32,
     pub created_at: i64,
+    pub live_athletes: u32, // Exclusive athletes not yet burned
 }
 
 // Fixed size for account allocation
@@ -27,7 +31,8 @@ impl CreatorAccount {
         2 + // creator_fee_basis_points
         64 + // collections_created (variable size, estimate)
         4 + // total_athletes_created
-        8; // created_at
+        8 + // created_at
+        4; // live_athletes
 }
 
 // Context for registering a creator
@@ -98,6 +103,23 @@ pub struct CreateExclusiveAthlete<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+// Context for closing a creator account
+#[derive(Accounts)]
+pub struct CloseCreator<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"creator", authority.key().as_ref()],
+        bump,
+        constraint = creator_account.authority == authority.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = creator_account.live_athletes == 0 @ ErrorCode::CreatorHasLiveAthletes
+    )]
+    pub creator_account: Account<'info, CreatorAccount>,
+}
+
 // Register a new creator
 pub fn register_creator(
     ctx: Context<RegisterCreator>,
@@ -119,6 +141,7 @@ pub fn register_creator(
     creator_account.collections_created = Vec::new();
     creator_account.total_athletes_created = 0;
     creator_account.created_at = clock.unix_timestamp;
+    creator_account.live_athletes = 0;
     
     Ok(())
 }
@@ -138,80 +161,69 @@ pub fn verify_creator(ctx: Context<VerifyCreator>) -> Result<()> {
 pub fn create_exclusive_athlete(
     ctx: Context<CreateExclusiveAthlete>,
     name: String,
-    position: String,
+    position: Position,
     uri: String,
     predefined_stats: Option<PlayerStats>,
     collection_id: Option<Pubkey>,
     max_editions: Option<u64>,
 ) -> Result<()> {
     let creator_account = &mut ctx.accounts.creator_account;
-    let player_account = &mut ctx.accounts.player_account;
     let clock = Clock::get()?;
     
-    // Initialize basic player data
-    player_account.owner = ctx.accounts.payer.key();
-    player_account.mint = ctx.accounts.mint.key();
-    player_account.name = name;
-    player_account.position = position;
-    player_account.created_at = clock.unix_timestamp;
-    player_account.last_updated = clock.unix_timestamp;
-    player_account.uri = uri;
-    player_account.team = None;
-    player_account.creator = Some(creator_account.key());
-    player_account.is_exclusive = true;
+    // Exclusive athletes start slightly older (18-23)
+    let starting_age = 18 + (get_random_value(&clock, 7) % 6) as u8;
+    let mut player = PlayerAccount::new(
+        ctx.accounts.payer.key(),
+        ctx.accounts.mint.key(),
+        name,
+        position,
+        uri,
+        starting_age,
+        clock.unix_timestamp,
+    );
+    player.creator = Some(creator_account.key());
+    player.is_exclusive = true;
+    
+    // Potential comes from the creator's design or a higher roll (70-100 range); only the range
+    // around it is public until a reporter seals the true value
+    let potential = match &predefined_stats {
+        Some(stats) => stats.potential,
+        None => 70 + (get_random_value(&clock, 5) % 31) as u8,
+    };
     
     // Initialize stats - either from predefined values or with higher base values
     if let Some(stats) = predefined_stats {
         // Use predefined stats from the creator
-        player_account.mechanical = stats.mechanical;
-        player_account.game_knowledge = stats.game_knowledge;
-        player_account.team_communication = stats.team_communication;
-        player_account.adaptability = stats.adaptability;
-        player_account.consistency = stats.consistency;
-        player_account.form = stats.form;
-        player_account.potential = stats.potential;
+        player.mechanical = stats.mechanical;
+        player.game_knowledge = stats.game_knowledge;
+        player.team_communication = stats.team_communication;
+        player.adaptability = stats.adaptability;
+        player.consistency = stats.consistency;
+        player.form = stats.form;
     } else {
         // Exclusive athletes have higher base stats (60-90 range instead of 50-80)
-        player_account.mechanical = 60 + (get_random_value(&clock, 0) % 31) as u8;
-        player_account.game_knowledge = 60 + (get_random_value(&clock, 1) % 31) as u8;
-        player_account.team_communication = 60 + (get_random_value(&clock, 2) % 31) as u8;
-        player_account.adaptability = 60 + (get_random_value(&clock, 3) % 31) as u8;
-        player_account.consistency = 60 + (get_random_value(&clock, 4) % 31) as u8;
-        player_account.form = 80; // Higher starting form
-        player_account.potential = 70 + (get_random_value(&clock, 5) % 31) as u8; // 70-100 range
+        player.mechanical = 60 + (get_random_value(&clock, 0) % 31) as u8;
+        player.game_knowledge = 60 + (get_random_value(&clock, 1) % 31) as u8;
+        player.team_communication = 60 + (get_random_value(&clock, 2) % 31) as u8;
+        player.adaptability = 60 + (get_random_value(&clock, 3) % 31) as u8;
+        player.consistency = 60 + (get_random_value(&clock, 4) % 31) as u8;
+        player.form = 80; // Higher starting form
     }
     
-    // Initialize game-specific data (empty for now)
-    player_account.game_specific_data = Vec::new();
-    
     // Initialize with a special creator ability
-    player_account.special_abilities = vec![
-        SpecialAbility {
-            name: format!("{} Special", creator_account.name),
-            value: 75 + (get_random_value(&clock, 6) % 26) as u8, // 75-100 range
-        }
-    ];
-    
-    // Initialize performance metrics
-    player_account.experience = 0;
-    player_account.matches_played = 0;
-    player_account.wins = 0;
-    player_account.mvp_count = 0;
-    
-    // Initialize history
-    player_account.performance_history = Vec::new();
+    player.special_abilities = vec![SpecialAbility::unlocked(CREATOR_SIGNATURE_ID)];
     
     // Set rarity based on potential (exclusive athletes are minimum Rare)
-    if player_account.potential >= 90 {
-        player_account.rarity = Rarity::Legendary;
-    } else if player_account.potential >= 80 {
-        player_account.rarity = Rarity::Epic;
-    } else {
-        player_account.rarity = Rarity::Rare;
-    }
+    player.set_minted_potential(
+        Rarity::from_potential(potential).at_least(Rarity::Rare),
+        potential_window(potential),
+    );
+    
+    ctx.accounts.player_account.set_inner(player);
     
     // Update creator stats
     creator_account.total_athletes_created += 1;
+    creator_account.live_athletes += 1;
     
     // If this is part of a collection, add to collections created if new
     if let Some(collection) = collection_id {
@@ -223,6 +235,13 @@ pub fn create_exclusive_athlete(
     Ok(())
 }
 
+// Close a creator account once none of its athletes are live, returning rent
+pub fn close_creator(_ctx: Context<CloseCreator>) -> Result<()> {
+    msg!("Creator account closed");
+    
+    Ok(())
+}
+
 // Helper for random value generation
 fn get_random_value(clock: &Clock, seed: u8) -> u64 {
     let mut bytes = [0u8; 8];
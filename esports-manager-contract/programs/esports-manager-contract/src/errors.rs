@@ -49,4 +49,235 @@ pub enum ErrorCode {
     
     #[msg("Invalid tournament ID")]
     InvalidTournamentId,
-}
\ No newline at end of file
+    
+    #[msg("Player is currently in a training program")]
+    PlayerInTraining,
+    
+    #[msg("Player has no active training program")]
+    NoActiveTraining,
+    
+    #[msg("Training program has not finished yet")]
+    TrainingNotComplete,
+    
+    #[msg("Lineup does not match the team roster")]
+    InvalidLineup,
+    
+    #[msg("Invalid ability definition")]
+    InvalidAbilityDefinition,
+    
+    #[msg("Player rarity is too low for this ability")]
+    RarityTooLow,
+    
+    #[msg("Player has not met the unlock conditions for this ability")]
+    AbilityNotUnlocked,
+    
+    #[msg("Player does not have this ability")]
+    AbilityNotFound,
+    
+    #[msg("Ability is already at its maximum level")]
+    AbilityMaxLevel,
+    
+    #[msg("Upgrade amount must be greater than zero")]
+    InvalidUpgradeAmount,
+    
+    #[msg("Player does not have enough experience")]
+    InsufficientExperience,
+    
+    #[msg("Abilities have already been respecced this season")]
+    RespecCooldown,
+    
+    #[msg("Player is retired")]
+    PlayerRetired,
+    
+    #[msg("Player is past the maximum playing age")]
+    PlayerTooOld,
+    
+    #[msg("Player is injured")]
+    PlayerInjured,
+    
+    #[msg("Invalid hero pool")]
+    InvalidHeroPool,
+    
+    #[msg("Metadata account does not belong to this player")]
+    InvalidMetadataAccount,
+    
+    #[msg("Player metadata was refreshed too recently")]
+    MetadataRefreshCooldown,
+    
+    #[msg("Creator account does not match the player's creator")]
+    InvalidCreatorAccount,
+    
+    #[msg("Creator still has live athletes")]
+    CreatorHasLiveAthletes,
+    
+    #[msg("Team roster must be empty")]
+    TeamNotEmpty,
+    
+    #[msg("Tournament is not completed or canceled")]
+    TournamentNotFinished,
+    
+    #[msg("Tournament prizes have not all been claimed")]
+    PrizesUnclaimed,
+    
+    #[msg("Exclusive athletes cannot be fused")]
+    ExclusiveAthleteNotFusible,
+    
+    #[msg("A player cannot be fused with itself")]
+    InvalidFusion,
+    
+    #[msg("Invalid pack definition")]
+    InvalidPackDefinition,
+    
+    #[msg("Pack is not on sale")]
+    PackNotAvailable,
+    
+    #[msg("Pack accounts do not match the pack contents")]
+    InvalidPackAccounts,
+    
+    #[msg("Player potential has not been committed")]
+    PotentialNotCommitted,
+    
+    #[msg("Player potential has already been revealed")]
+    PotentialAlreadyRevealed,
+    
+    #[msg("Player has not played enough matches to reveal potential")]
+    RevealTooEarly,
+    
+    #[msg("Scouting range is invalid")]
+    InvalidScoutRange,
+    
+    #[msg("Scouting report has already been fulfilled")]
+    ScoutReportFulfilled,
+    
+    #[msg("Invalid contract terms")]
+    InvalidContractTerms,
+    
+    #[msg("No salary is due on this contract")]
+    NoSalaryDue,
+    
+    #[msg("Team treasury cannot cover the salary")]
+    InsufficientTeamFunds,
+    
+    #[msg("Contract has not expired yet")]
+    ContractActive,
+    
+    #[msg("Contract has unpaid salary")]
+    SalaryOutstanding,
+    
+    #[msg("Invalid loan terms")]
+    InvalidLoanTerms,
+    
+    #[msg("Loan has already been accepted")]
+    LoanAlreadyActive,
+    
+    #[msg("Loan is not active")]
+    LoanNotActive,
+    
+    #[msg("Loan period has not ended")]
+    LoanNotEnded,
+    
+    #[msg("Player's use rights are rented out")]
+    PlayerRented,
+    
+    #[msg("Invalid rental terms")]
+    InvalidRentalTerms,
+    
+    #[msg("Player is not currently rented")]
+    RentalNotActive,
+    
+    #[msg("Rental period has not ended")]
+    RentalNotEnded,
+    
+    #[msg("No rental fees are due")]
+    NoRentalFeesDue,
+    
+    #[msg("Escrow account does not hold enough lamports")]
+    EscrowUnderfunded,
+    
+    #[msg("Invalid sale")]
+    InvalidSale,
+    
+    #[msg("Invalid auction parameters")]
+    InvalidAuction,
+    
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    
+    #[msg("Bid is below the minimum")]
+    BidTooLow,
+    
+    #[msg("Bidder account does not match the auction")]
+    InvalidBidder,
+    
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    
+    #[msg("Invalid trade")]
+    InvalidTrade,
+    
+    #[msg("Trade accounts do not match the proposal")]
+    InvalidTradeAccounts,
+    
+    #[msg("Roster accounts do not match the team roster")]
+    InvalidRosterAccounts,
+    
+    #[msg("Metadata accounts are required for players with on-chain metadata")]
+    MetadataAccountsRequired,
+    
+    #[msg("Match id is too long")]
+    InvalidMatchId,
+    
+    #[msg("Team account is already on the current layout")]
+    TeamAlreadyMigrated,
+    
+    #[msg("Player is in escrow for a sale, auction or trade")]
+    PlayerEscrowed,
+    
+    #[msg("Pack can only be revealed after the slot it was bought in")]
+    PackNotRevealable,
+    
+    #[msg("Contract salary is not far enough behind to terminate")]
+    ContractInGoodStanding,
+    
+    #[msg("Tournament prize has already been claimed")]
+    PrizeAlreadyClaimed,
+    
+    #[msg("Only the tournament champion can claim the prize")]
+    NotTournamentChampion,
+    
+    #[msg("Tournament is already completed or canceled")]
+    TournamentAlreadyFinished,
+    
+    #[msg("Tournament has not been canceled")]
+    TournamentNotCanceled,
+    
+    #[msg("Team is not registered for this tournament")]
+    TeamNotRegistered,
+    
+    #[msg("Player potential has already been sealed")]
+    PotentialAlreadyCommitted,
+    
+    #[msg("Revealed potential does not match the commitment")]
+    InvalidPotentialReveal,
+    
+    #[msg("Built-in abilities can't be respecced")]
+    BuiltinAbilityRespec,
+    
+    #[msg("Ability id is reserved for a built-in ability")]
+    ReservedAbilityId,
+    
+    #[msg("Player has no free ability slots")]
+    AbilitySlotsFull,
+    
+    #[msg("Hero mastery must be between 0 and 100")]
+    InvalidHeroMastery,
+    
+    #[msg("Player account is already on the current layout")]
+    PlayerAlreadyMigrated,
+    
+    #[msg("Fusion can only be revealed after the slot it was committed in")]
+    FusionNotRevealable,
+}
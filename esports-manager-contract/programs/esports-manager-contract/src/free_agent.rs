@@ -0,0 +1,315 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::contract::{PlayerContract, MAX_CONTRACT_PERIODS};
+use crate::player::{PlayerAccount, Position};
+use crate::team::{join_team, SigningTerms, TeamAccount};
+
+// Free Agent Listing Structure (one per listed player)
+#[account]
+pub struct FreeAgentListing {
+    pub player: Pubkey,
+    pub player_mint: Pubkey,
+    pub owner: Pubkey,
+    pub position: Position,
+    pub asking_salary: u64,
+    pub listed_at: i64,
+}
+
+// Fixed size for account allocation
+impl FreeAgentListing {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // player pubkey
+        32 + // player_mint pubkey
+        32 + // owner pubkey
+        1 + // position
+        8 + // asking_salary
+        8; // listed_at
+}
+
+// Contract Offer Structure (one per team and player)
+#[account]
+pub struct ContractOffer {
+    pub team: Pubkey,
+    pub team_owner: Pubkey,
+    pub player: Pubkey,
+    pub position: Position,
+    pub salary_per_period: u64,
+    pub periods: u16,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl ContractOffer {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // team pubkey
+        32 + // team_owner pubkey
+        32 + // player pubkey
+        1 + // position
+        8 + // salary_per_period
+        2 + // periods
+        8; // created_at
+}
+
+// Context for listing a teamless player as a free agent
+#[derive(Accounts)]
+pub struct ListFreeAgent<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = FreeAgentListing::LEN,
+        seeds = [b"free_agent", player_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, FreeAgentListing>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for taking a player off the free-agent market
+#[derive(Accounts)]
+pub struct DelistFreeAgent<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"free_agent", listing.player.as_ref()],
+        bump,
+        constraint = listing.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub listing: Account<'info, FreeAgentListing>,
+}
+
+// Context for a team owner making an offer to a free agent
+#[derive(Accounts)]
+pub struct SubmitContractOffer<'info> {
+    #[account(mut)]
+    pub team_owner: Signer<'info>,
+    
+    #[account(
+        constraint = team_account.owner == team_owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        seeds = [b"free_agent", listing.player.as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, FreeAgentListing>,
+    
+    #[account(
+        init,
+        payer = team_owner,
+        space = ContractOffer::LEN,
+        seeds = [b"offer", team_account.key().as_ref(), listing.player.as_ref()],
+        bump
+    )]
+    pub contract_offer: Account<'info, ContractOffer>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for a team owner withdrawing an offer
+#[derive(Accounts)]
+pub struct WithdrawContractOffer<'info> {
+    #[account(mut)]
+    pub team_owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = team_owner,
+        seeds = [b"offer", contract_offer.team.as_ref(), contract_offer.player.as_ref()],
+        bump,
+        constraint = contract_offer.team_owner == team_owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub contract_offer: Account<'info, ContractOffer>,
+}
+
+// Context for a player owner rejecting an offer
+#[derive(Accounts)]
+pub struct RejectContractOffer<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        mut,
+        close = team_owner,
+        seeds = [b"offer", contract_offer.team.as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub contract_offer: Account<'info, ContractOffer>,
+    
+    /// CHECK: Gets the offer rent back
+    #[account(
+        mut,
+        address = contract_offer.team_owner
+    )]
+    pub team_owner: UncheckedAccount<'info>,
+}
+
+// Context for a player owner accepting an offer
+#[derive(Accounts)]
+pub struct AcceptContractOffer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    // The offer's rent goes back to the team owner who paid for it
+    #[account(
+        mut,
+        close = team_owner,
+        seeds = [b"offer", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub contract_offer: Account<'info, ContractOffer>,
+    
+    /// CHECK: Gets the offer rent back
+    #[account(
+        mut,
+        address = contract_offer.team_owner
+    )]
+    pub team_owner: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"free_agent", player_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, FreeAgentListing>,
+    
+    #[account(
+        mut,
+        constraint = team_account.owner == contract_offer.team_owner @ ErrorCode::UnauthorizedAccess
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PlayerContract::LEN,
+        seeds = [b"contract", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// List a teamless player on the free-agent market
+pub fn list_free_agent(ctx: Context<ListFreeAgent>, asking_salary: u64) -> Result<()> {
+    let player_account = &ctx.accounts.player_account;
+    let listing = &mut ctx.accounts.listing;
+    let clock = Clock::get()?;
+    
+    listing.player = player_account.key();
+    listing.player_mint = player_account.mint;
+    listing.owner = ctx.accounts.owner.key();
+    listing.position = player_account.position;
+    listing.asking_salary = asking_salary;
+    listing.listed_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+// Take a player off the market (outstanding offers can still be withdrawn by their teams)
+pub fn delist_free_agent(_ctx: Context<DelistFreeAgent>) -> Result<()> {
+    Ok(())
+}
+
+// Offer a listed free agent a contract for a roster position
+pub fn submit_contract_offer(
+    ctx: Context<SubmitContractOffer>,
+    position: Position,
+    salary_per_period: u64,
+    periods: u16,
+) -> Result<()> {
+    require!(
+        periods > 0 && periods <= MAX_CONTRACT_PERIODS,
+        ErrorCode::InvalidContractTerms
+    );
+    
+    let contract_offer = &mut ctx.accounts.contract_offer;
+    let clock = Clock::get()?;
+    
+    contract_offer.team = ctx.accounts.team_account.key();
+    contract_offer.team_owner = ctx.accounts.team_owner.key();
+    contract_offer.player = ctx.accounts.listing.player;
+    contract_offer.position = position;
+    contract_offer.salary_per_period = salary_per_period;
+    contract_offer.periods = periods;
+    contract_offer.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+pub fn withdraw_contract_offer(_ctx: Context<WithdrawContractOffer>) -> Result<()> {
+    Ok(())
+}
+
+pub fn reject_contract_offer(_ctx: Context<RejectContractOffer>) -> Result<()> {
+    msg!("Contract offer rejected");
+    
+    Ok(())
+}
+
+// Accept an offer: signs the contract and joins the roster in one step
+pub fn accept_contract_offer(ctx: Context<AcceptContractOffer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let contract_offer = &ctx.accounts.contract_offer;
+    let player_mint = ctx.accounts.player_account.mint;
+    
+    // A rented-out player's owner can't sign them elsewhere
+    require!(
+        ctx.accounts.player_account.active_user(clock.unix_timestamp).is_none(),
+        ErrorCode::PlayerRented
+    );
+    
+    join_team(
+        &mut ctx.accounts.team_account,
+        &mut ctx.accounts.player_account,
+        &mut ctx.accounts.player_contract,
+        player_mint,
+        SigningTerms {
+            position: contract_offer.position,
+            salary_per_period: contract_offer.salary_per_period,
+            contract_periods: contract_offer.periods,
+        },
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )?;
+    
+    msg!("{} signed with a new team", ctx.accounts.player_account.name);
+    
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use crate::errors::ErrorCode;
+use crate::pack::{find_slot_hash, slot_hash_roll};
+use crate::player::{potential_window, PlayerAccount, Rarity, SpecialAbility, MAX_SPECIAL_ABILITIES};
+
+// Most abilities a fused athlete inherits (highest levels win)
+pub const MAX_INHERITED_ABILITIES: usize = MAX_SPECIAL_ABILITIES;
+
+// Percent chance the child jumps a rarity tier (duplicates of the same tier fuse better)
+pub const FUSION_TIER_UP_CHANCE: u64 = 10;
+pub const FUSION_DUPLICATE_TIER_UP_CHANCE: u64 = 25;
+
+// Fusion Commit Structure (two burned parents waiting for the child's rolls to be revealed)
+#[account]
+pub struct FusionCommit {
+    pub owner: Pubkey,
+    pub parent_a: PlayerAccount,
+    pub parent_b: PlayerAccount,
+    pub commit_slot: u64, // Rolls are seeded by this slot's hash, unknown when fusing
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl FusionCommit {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // owner pubkey
+        (PlayerAccount::LEN - 8) + // parent_a
+        (PlayerAccount::LEN - 8) + // parent_b
+        8 + // commit_slot
+        8; // created_at
+}
+
+// Context for fusing two players into a new athlete
+#[derive(Accounts)]
+pub struct FusePlayers<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"player", player_a.mint.as_ref()],
+        bump,
+        constraint = player_a.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_a.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_a.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_a.is_in_training() @ ErrorCode::PlayerInTraining,
+        constraint = !player_a.is_exclusive @ ErrorCode::ExclusiveAthleteNotFusible,
+        constraint = player_a.user.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_a: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"player", player_b.mint.as_ref()],
+        bump,
+        constraint = player_b.mint != player_a.mint @ ErrorCode::InvalidFusion,
+        constraint = player_b.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_b.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_b.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_b.is_in_training() @ ErrorCode::PlayerInTraining,
+        constraint = !player_b.is_exclusive @ ErrorCode::ExclusiveAthleteNotFusible,
+        constraint = player_b.user.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_b: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(mut, address = player_a.mint)]
+    pub mint_a: Box<Account<'info, Mint>>,
+    
+    #[account(mut, address = player_b.mint)]
+    pub mint_b: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::authority = owner
+    )]
+    pub token_account_a: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::authority = owner
+    )]
+    pub token_account_b: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = FusionCommit::LEN,
+        seeds = [b"fusion", player_a.mint.as_ref()],
+        bump
+    )]
+    pub fusion: Box<Account<'info, FusionCommit>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Context for minting the athlete of a committed fusion
+#[derive(Accounts)]
+pub struct RevealFusion<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ ErrorCode::UnauthorizedAccess
+    )]
+    pub fusion: Box<Account<'info, FusionCommit>>,
+    
+    // Mint of the fused athlete's NFT
+    pub new_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PlayerAccount::LEN,
+        seeds = [b"player", new_mint.key().as_ref()],
+        bump
+    )]
+    pub new_player: Box<Account<'info, PlayerAccount>>,
+    
+    /// CHECK: SlotHashes sysvar, read by hand (too large to deserialize)
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Burn two players; the fused athlete is rolled and minted by reveal_fusion from a later slot
+pub fn fuse_players(ctx: Context<FusePlayers>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Both parent NFTs must be held and are burned
+    for (mint, from) in [
+        (&ctx.accounts.mint_a, &ctx.accounts.token_account_a),
+        (&ctx.accounts.mint_b, &ctx.accounts.token_account_b),
+    ] {
+        require!(from.amount > 0, ErrorCode::UnauthorizedAccess);
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            from.amount,
+        )?;
+    }
+    
+    // The parents' accounts close with this instruction, so the commit keeps what the child inherits
+    let fusion = &mut ctx.accounts.fusion;
+    fusion.owner = ctx.accounts.owner.key();
+    fusion.parent_a = ctx.accounts.player_a.as_ref().clone().into_inner();
+    fusion.parent_b = ctx.accounts.player_b.as_ref().clone().into_inner();
+    fusion.commit_slot = clock.slot;
+    fusion.created_at = clock.unix_timestamp;
+    
+    msg!("Fusing {} and {}", fusion.parent_a.name, fusion.parent_b.name);
+    
+    Ok(())
+}
+
+// Mint the fused athlete, rolling its blend from the hash of the fusion slot
+pub fn reveal_fusion(ctx: Context<RevealFusion>, name: String, uri: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let fusion = &ctx.accounts.fusion;
+    require!(clock.slot > fusion.commit_slot, ErrorCode::FusionNotRevealable);
+    
+    // Once the fusion slot drops out of SlotHashes every roll falls back to its floor,
+    // so sitting on an unwanted result never pays off
+    let entropy = find_slot_hash(&ctx.accounts.slot_hashes, fusion.commit_slot)?
+        .map(|slot_hash| hashv(&[slot_hash.as_ref(), fusion.key().as_ref()]).to_bytes());
+    let roll = |seed: u8| slot_hash_roll(entropy, &[], seed);
+    
+    let player_a = &fusion.parent_a;
+    let player_b = &fusion.parent_b;
+    let now = clock.unix_timestamp;
+    
+    // The parent with the higher stat total dominates the blend
+    let total = |p: &PlayerAccount| p.core_stats().iter().map(|s| *s as u16).sum::<u16>();
+    let (stronger, weaker) = if total(player_a) >= total(player_b) {
+        (player_a, player_b)
+    } else {
+        (player_b, player_a)
+    };
+    
+    // The fused athlete is as old as the younger parent, with a fresh career otherwise
+    let mut new_player = PlayerAccount::new(
+        ctx.accounts.owner.key(),
+        ctx.accounts.new_mint.key(),
+        name,
+        stronger.position,
+        uri,
+        std::cmp::min(player_a.age(now), player_b.age(now)),
+        now,
+    );
+    
+    // Each stat leans 60-90% toward the stronger parent, plus a small fusion bonus
+    let strong_stats = stronger.core_stats();
+    let weak_stats = weaker.core_stats();
+    let mut stats = [0u8; 5];
+    for i in 0..5 {
+        let weight = 60 + roll(30 + i as u8) % 31;
+        let blend = (strong_stats[i] as u64 * weight + weak_stats[i] as u64 * (100 - weight)) / 100;
+        let bonus = roll(35 + i as u8) % 4;
+        stats[i] = std::cmp::min(100, blend + bonus) as u8;
+    }
+    new_player.mechanical = stats[0];
+    new_player.game_knowledge = stats[1];
+    new_player.team_communication = stats[2];
+    new_player.adaptability = stats[3];
+    new_player.consistency = stats[4];
+    
+    // Potential carries over from the better parent's public estimate, with a chance to reach the next tier
+    // (the top of the roll range tiers up, so the expired-hash floor never does)
+    let mut potential = std::cmp::max(stronger.potential_estimate(), weaker.potential_estimate());
+    let tier_up_chance = if stronger.rarity == weaker.rarity {
+        FUSION_DUPLICATE_TIER_UP_CHANCE
+    } else {
+        FUSION_TIER_UP_CHANCE
+    };
+    if roll(40) % 100 >= 100 - tier_up_chance {
+        if let Some(threshold) = next_tier_potential(Rarity::from_potential(potential)) {
+            potential = threshold;
+        }
+    }
+    new_player.set_minted_potential(Rarity::from_potential(potential), potential_window(potential));
+    
+    // Abilities from both parents (best level per ability, strongest few kept)
+    let mut abilities: Vec<SpecialAbility> = Vec::new();
+    for ability in stronger.special_abilities.iter().chain(weaker.special_abilities.iter()) {
+        match abilities.iter_mut().find(|a| a.ability_id == ability.ability_id) {
+            Some(existing) => existing.level = std::cmp::max(existing.level, ability.level),
+            None => abilities.push(SpecialAbility::new(ability.ability_id, ability.level)),
+        }
+    }
+    abilities.sort_by_key(|a| std::cmp::Reverse(a.level));
+    abilities.truncate(MAX_INHERITED_ABILITIES);
+    new_player.special_abilities = abilities;
+    
+    // Hero pools merge, keeping the best mastery for shared heroes
+    for hero in stronger.game_specific_data.heroes.iter().chain(weaker.game_specific_data.heroes.iter()) {
+        let entry = new_player.game_specific_data.hero_entry(hero.hero_id);
+        entry.mastery = std::cmp::max(entry.mastery, hero.mastery);
+        entry.games_played = entry.games_played.saturating_add(hero.games_played);
+    }
+    
+    msg!(
+        "Fused {} and {} into {} ({}-{} potential)",
+        player_a.name,
+        player_b.name,
+        new_player.name,
+        new_player.potential_min,
+        new_player.potential_max
+    );
+    
+    ctx.accounts.new_player.set_inner(new_player);
+    
+    Ok(())
+}
+
+// Lowest potential of the tier above `rarity`
+fn next_tier_potential(rarity: Rarity) -> Option<u8> {
+    match rarity {
+        Rarity::Common => Some(60),
+        Rarity::Uncommon => Some(70),
+        Rarity::Rare => Some(80),
+        Rarity::Epic => Some(90),
+        Rarity::Legendary => None,
+    }
+}
@@ -3,7 +3,21 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use std::str::FromStr;
 
 // Import other modules
+pub mod config;
 pub mod player;
+pub mod ability;
+pub mod reporter;
+pub mod metadata;
+pub mod fusion;
+pub mod pack;
+pub mod scouting;
+pub mod contract;
+pub mod free_agent;
+pub mod loan;
+pub mod rental;
+pub mod marketplace;
+pub mod auction;
+pub mod trade;
 pub mod team;
 pub mod creator;
 pub mod tournament;
@@ -11,7 +25,21 @@ pub mod errors;
 pub mod utils;
 
 // Use components from modules
+use config::*;
 use player::*;
+use ability::*;
+use reporter::*;
+use metadata::*;
+use fusion::*;
+use pack::*;
+use scouting::*;
+use contract::*;
+use free_agent::*;
+use loan::*;
+use rental::*;
+use marketplace::*;
+use auction::*;
+use trade::*;
 use team::*;
 use creator::*;
 use tournament::*;
@@ -23,15 +51,20 @@ declare_id!("2KBakNVa6xLxp6uQsgHhikrknw1pkjkS2f6ZGKtV5BzZ");
 pub mod esports_manager {
     use super::*;
 
+    // Program Config Functions
+    pub fn initialize_config(ctx: Context<InitializeConfig>, authority: Pubkey) -> Result<()> {
+        config::initialize_config(ctx, authority)
+    }
+
     // Player Management Functions
     pub fn initialize_player(
         ctx: Context<InitializePlayer>,
         name: String,
-        position: String,
-        game_specific_data: Vec<u8>,
+        position: Position,
+        hero_pool: Vec<u16>,
         uri: String,
     ) -> Result<()> {
-        player::initialize_player(ctx, name, position, game_specific_data, uri)
+        player::initialize_player(ctx, name, position, hero_pool, uri)
     }
 
     pub fn update_player_performance(
@@ -72,12 +105,148 @@ pub mod esports_manager {
         player::train_player(ctx, training_type, intensity)
     }
 
-    pub fn add_special_ability(
-        ctx: Context<AddSpecialAbility>,
-        ability_name: String,
-        ability_value: u8,
+    pub fn start_training_program(
+        ctx: Context<StartTrainingProgram>,
+        program: TrainingProgram,
+    ) -> Result<()> {
+        player::start_training_program(ctx, program)
+    }
+
+    pub fn complete_training_program(ctx: Context<CompleteTrainingProgram>) -> Result<()> {
+        player::complete_training_program(ctx)
+    }
+
+    pub fn update_hero_mastery(
+        ctx: Context<UpdateHeroMastery>,
+        hero_id: u16,
+        mastery: u8,
+        games_played: u16,
+    ) -> Result<()> {
+        player::update_hero_mastery(ctx, hero_id, mastery, games_played)
+    }
+
+    pub fn refresh_player_metadata(ctx: Context<RefreshPlayerMetadata>) -> Result<()> {
+        metadata::refresh_player_metadata(ctx)
+    }
+
+    pub fn retire_player(ctx: Context<RetirePlayer>) -> Result<()> {
+        player::retire_player(ctx)
+    }
+
+    pub fn migrate_player_account(ctx: Context<MigratePlayerAccount>) -> Result<()> {
+        player::migrate_player_account(ctx)
+    }
+
+    pub fn add_special_ability(ctx: Context<AddSpecialAbility>) -> Result<()> {
+        player::add_special_ability(ctx)
+    }
+
+    pub fn close_player(ctx: Context<ClosePlayer>) -> Result<()> {
+        player::close_player(ctx)
+    }
+
+    pub fn fuse_players(ctx: Context<FusePlayers>) -> Result<()> {
+        fusion::fuse_players(ctx)
+    }
+
+    pub fn reveal_fusion(ctx: Context<RevealFusion>, name: String, uri: String) -> Result<()> {
+        fusion::reveal_fusion(ctx, name, uri)
+    }
+
+    // Pack Store Functions
+    pub fn create_pack_definition(
+        ctx: Context<CreatePackDefinition>,
+        pack_id: u32,
+        name: String,
+        price: u64,
+        players_per_pack: u8,
+        rarity_odds: [u16; 5],
+    ) -> Result<()> {
+        pack::create_pack_definition(ctx, pack_id, name, price, players_per_pack, rarity_odds)
+    }
+
+    pub fn update_pack_definition(
+        ctx: Context<UpdatePackDefinition>,
+        name: String,
+        price: u64,
+        players_per_pack: u8,
+        rarity_odds: [u16; 5],
+        active: bool,
     ) -> Result<()> {
-        player::add_special_ability(ctx, ability_name, ability_value)
+        pack::update_pack_definition(ctx, name, price, players_per_pack, rarity_odds, active)
+    }
+
+    pub fn open_pack(ctx: Context<OpenPack>) -> Result<()> {
+        pack::open_pack(ctx)
+    }
+
+    pub fn reveal_pack<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealPack<'info>>,
+        uris: Vec<String>,
+    ) -> Result<()> {
+        pack::reveal_pack(ctx, uris)
+    }
+
+    // Scouting Functions
+    pub fn seal_potential(ctx: Context<SealPotential>, commitment: [u8; 32]) -> Result<()> {
+        scouting::seal_potential(ctx, commitment)
+    }
+
+    pub fn scout_player(ctx: Context<ScoutPlayer>) -> Result<()> {
+        scouting::scout_player(ctx)
+    }
+
+    pub fn fulfill_scout_report(
+        ctx: Context<FulfillScoutReport>,
+        potential_min: u8,
+        potential_max: u8,
+    ) -> Result<()> {
+        scouting::fulfill_scout_report(ctx, potential_min, potential_max)
+    }
+
+    pub fn reveal_potential(ctx: Context<RevealPotential>, potential: u8, salt: [u8; 32]) -> Result<()> {
+        scouting::reveal_potential(ctx, potential, salt)
+    }
+
+    // Ability Catalog Functions
+    pub fn create_ability_definition(
+        ctx: Context<CreateAbilityDefinition>,
+        ability_id: u16,
+        params: AbilityDefinitionParams,
+    ) -> Result<()> {
+        ability::create_ability_definition(ctx, ability_id, params)
+    }
+
+    pub fn update_ability_definition(
+        ctx: Context<UpdateAbilityDefinition>,
+        params: AbilityDefinitionParams,
+    ) -> Result<()> {
+        ability::update_ability_definition(ctx, params)
+    }
+
+    pub fn upgrade_ability(
+        ctx: Context<UpgradeAbility>,
+        ability_id: u16,
+        levels: u8,
+        payment: UpgradePayment,
+    ) -> Result<()> {
+        ability::upgrade_ability(ctx, ability_id, levels, payment)
+    }
+
+    pub fn respec_abilities(
+        ctx: Context<RespecAbilities>,
+        ability_ids: Vec<u16>,
+    ) -> Result<()> {
+        ability::respec_abilities(ctx, ability_ids)
+    }
+
+    // Reporter Functions
+    pub fn authorize_reporter(ctx: Context<AuthorizeReporter>, reporter: Pubkey) -> Result<()> {
+        reporter::authorize_reporter(ctx, reporter)
+    }
+
+    pub fn revoke_reporter(ctx: Context<RevokeReporter>) -> Result<()> {
+        reporter::revoke_reporter(ctx)
     }
 
     // Team Management Functions
@@ -92,9 +261,11 @@ pub mod esports_manager {
     pub fn add_player_to_team(
         ctx: Context<AddPlayerToTeam>,
         player_mint: Pubkey,
-        position: String
+        position: Position,
+        salary_per_period: u64,
+        contract_periods: u16,
     ) -> Result<()> {
-        team::add_player_to_team(ctx, player_mint, position)
+        team::add_player_to_team(ctx, player_mint, position, salary_per_period, contract_periods)
     }
 
     pub fn remove_player_from_team(
@@ -104,6 +275,198 @@ pub mod esports_manager {
         team::remove_player_from_team(ctx, player_mint)
     }
 
+    pub fn refresh_team_stats(ctx: Context<RefreshTeamStats>) -> Result<()> {
+        team::refresh_team_stats(ctx)
+    }
+
+    pub fn migrate_team_account(ctx: Context<MigrateTeamAccount>) -> Result<()> {
+        team::migrate_team_account(ctx)
+    }
+
+    pub fn close_team(ctx: Context<CloseTeam>) -> Result<()> {
+        team::close_team(ctx)
+    }
+
+    // Contracts and Payroll Functions
+    pub fn fund_team_treasury(ctx: Context<FundTeamTreasury>, amount: u64) -> Result<()> {
+        contract::fund_team_treasury(ctx, amount)
+    }
+
+    pub fn withdraw_team_treasury(ctx: Context<WithdrawTeamTreasury>, amount: u64) -> Result<()> {
+        contract::withdraw_team_treasury(ctx, amount)
+    }
+
+    pub fn run_payroll(ctx: Context<RunPayroll>) -> Result<()> {
+        contract::run_payroll(ctx)
+    }
+
+    pub fn expire_contract(ctx: Context<ExpireContract>) -> Result<()> {
+        contract::expire_contract(ctx)
+    }
+
+    pub fn terminate_unpaid_contract(ctx: Context<TerminateUnpaidContract>) -> Result<()> {
+        contract::terminate_unpaid_contract(ctx)
+    }
+
+    // Free Agent Market Functions
+    pub fn list_free_agent(ctx: Context<ListFreeAgent>, asking_salary: u64) -> Result<()> {
+        free_agent::list_free_agent(ctx, asking_salary)
+    }
+
+    pub fn delist_free_agent(ctx: Context<DelistFreeAgent>) -> Result<()> {
+        free_agent::delist_free_agent(ctx)
+    }
+
+    pub fn submit_contract_offer(
+        ctx: Context<SubmitContractOffer>,
+        position: Position,
+        salary_per_period: u64,
+        periods: u16,
+    ) -> Result<()> {
+        free_agent::submit_contract_offer(ctx, position, salary_per_period, periods)
+    }
+
+    pub fn withdraw_contract_offer(ctx: Context<WithdrawContractOffer>) -> Result<()> {
+        free_agent::withdraw_contract_offer(ctx)
+    }
+
+    pub fn reject_contract_offer(ctx: Context<RejectContractOffer>) -> Result<()> {
+        free_agent::reject_contract_offer(ctx)
+    }
+
+    pub fn accept_contract_offer(ctx: Context<AcceptContractOffer>) -> Result<()> {
+        free_agent::accept_contract_offer(ctx)
+    }
+
+    // Player Loan Functions
+    pub fn propose_loan(
+        ctx: Context<ProposeLoan>,
+        position: Position,
+        fee: u64,
+        duration: i64,
+    ) -> Result<()> {
+        loan::propose_loan(ctx, position, fee, duration)
+    }
+
+    pub fn cancel_loan(ctx: Context<CancelLoan>) -> Result<()> {
+        loan::cancel_loan(ctx)
+    }
+
+    pub fn accept_loan(ctx: Context<AcceptLoan>) -> Result<()> {
+        loan::accept_loan(ctx)
+    }
+
+    pub fn return_loaned_player(ctx: Context<ReturnLoanedPlayer>) -> Result<()> {
+        loan::return_loaned_player(ctx)
+    }
+
+    // Player Rental Functions
+    pub fn list_player_for_rent(
+        ctx: Context<ListPlayerForRent>,
+        fee_per_period: u64,
+        max_periods: u16,
+    ) -> Result<()> {
+        rental::list_player_for_rent(ctx, fee_per_period, max_periods)
+    }
+
+    pub fn cancel_rental_listing(ctx: Context<CancelRentalListing>) -> Result<()> {
+        rental::cancel_rental_listing(ctx)
+    }
+
+    pub fn rent_player(ctx: Context<RentPlayer>, periods: u16) -> Result<()> {
+        rental::rent_player(ctx, periods)
+    }
+
+    pub fn claim_rental_fees(ctx: Context<ClaimRentalFees>) -> Result<()> {
+        rental::claim_rental_fees(ctx)
+    }
+
+    pub fn end_rental(ctx: Context<EndRental>) -> Result<()> {
+        rental::end_rental(ctx)
+    }
+
+    // Marketplace Functions
+    pub fn list_player(ctx: Context<ListPlayer>, price: u64) -> Result<()> {
+        marketplace::list_player(ctx, price)
+    }
+
+    pub fn delist_player(ctx: Context<DelistPlayer>) -> Result<()> {
+        marketplace::delist_player(ctx)
+    }
+
+    pub fn buy_player(ctx: Context<BuyPlayer>) -> Result<()> {
+        marketplace::buy_player(ctx)
+    }
+
+    pub fn make_purchase_offer(ctx: Context<MakePurchaseOffer>, amount: u64) -> Result<()> {
+        marketplace::make_purchase_offer(ctx, amount)
+    }
+
+    pub fn cancel_purchase_offer(ctx: Context<CancelPurchaseOffer>) -> Result<()> {
+        marketplace::cancel_purchase_offer(ctx)
+    }
+
+    pub fn accept_purchase_offer(ctx: Context<AcceptPurchaseOffer>) -> Result<()> {
+        marketplace::accept_purchase_offer(ctx)
+    }
+
+    // Auction Functions
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        kind: AuctionKind,
+        start_price: u64,
+        end_price: u64,
+        min_increment: u64,
+        duration: i64,
+    ) -> Result<()> {
+        auction::create_auction(ctx, kind, start_price, end_price, min_increment, duration)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        auction::place_bid(ctx, amount)
+    }
+
+    pub fn buy_dutch_auction(ctx: Context<BuyDutchAuction>) -> Result<()> {
+        auction::buy_dutch_auction(ctx)
+    }
+
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        auction::settle_auction(ctx)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        auction::cancel_auction(ctx)
+    }
+
+    // Trade Functions
+    pub fn propose_trade<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProposeTrade<'info>>,
+        trade_id: u64,
+        requested_players: Vec<Pubkey>,
+        proposer_lamports: u64,
+        counterparty_lamports: u64,
+        proposer_tokens: u64,
+        counterparty_tokens: u64,
+    ) -> Result<()> {
+        trade::propose_trade(
+            ctx,
+            trade_id,
+            requested_players,
+            proposer_lamports,
+            counterparty_lamports,
+            proposer_tokens,
+            counterparty_tokens,
+        )
+    }
+
+    pub fn accept_trade<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptTrade<'info>>) -> Result<()> {
+        trade::accept_trade(ctx)
+    }
+
+    pub fn cancel_trade<'info>(ctx: Context<'_, '_, 'info, 'info, CancelTrade<'info>>) -> Result<()> {
+        trade::cancel_trade(ctx)
+    }
+
     pub fn register_creator(
         ctx: Context<RegisterCreator>,
         name: String,
@@ -119,10 +482,14 @@ pub mod esports_manager {
         creator::verify_creator(ctx)
     }
 
+    pub fn close_creator(ctx: Context<CloseCreator>) -> Result<()> {
+        creator::close_creator(ctx)
+    }
+
     pub fn create_exclusive_athlete(
         ctx: Context<CreateExclusiveAthlete>,
         name: String,
-        position: String,
+        position: Position,
         uri: String,
         predefined_stats: Option<PlayerStats>,
         collection_id: Option<Pubkey>,
@@ -150,16 +517,16 @@ pub mod esports_manager {
         tournament::create_tournament(ctx, name, entry_fee, start_time, max_teams)
     }
 
-    pub fn register_team_for_tournament(
-        ctx: Context<RegisterTeamForTournament>,
+    pub fn register_team_for_tournament<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterTeamForTournament<'info>>,
         tournament_id: Pubkey,
         team_id: Pubkey
     ) -> Result<()> {
         tournament::register_team(ctx, tournament_id, team_id)
     }
 
-    pub fn record_match_result(
-        ctx: Context<RecordMatchResult>,
+    pub fn record_match_result<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordMatchResult<'info>>,
         match_id: String,
         winner_id: Pubkey,
         loser_id: Pubkey,
@@ -168,4 +535,20 @@ pub mod esports_manager {
     ) -> Result<()> {
         tournament::record_match_result(ctx, match_id, winner_id, loser_id, score, match_data)
     }
+
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        tournament::claim_tournament_prize(ctx)
+    }
+
+    pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+        tournament::cancel_tournament(ctx)
+    }
+
+    pub fn claim_entry_refund(ctx: Context<ClaimEntryRefund>) -> Result<()> {
+        tournament::claim_entry_refund(ctx)
+    }
+
+    pub fn close_tournament(ctx: Context<CloseTournament>) -> Result<()> {
+        tournament::close_tournament(ctx)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+use crate::player::{PlayerAccount, Position};
+use crate::team::{add_to_roster, is_player_on_team, refresh_roster_statistics, remove_from_roster, TeamAccount};
+
+// Loan length bounds
+pub const MIN_LOAN_DURATION: i64 = 86400;
+pub const MAX_LOAN_DURATION: i64 = 90 * 86400;
+
+// Player Loan Structure (one per player; the player keeps their parent roster slot)
+#[account]
+pub struct PlayerLoan {
+    pub player: Pubkey,
+    pub player_mint: Pubkey,
+    pub parent_team: Pubkey,
+    pub parent_owner: Pubkey,
+    pub borrower_team: Pubkey,
+    pub position: Position, // Position at the borrowing team
+    pub fee: u64,           // Escrowed in this account until the loan ends
+    pub duration: i64,
+    pub status: LoanStatus,
+    pub created_at: i64,
+    pub ends_at: Option<i64>,
+}
+
+// Fixed size for account allocation
+impl PlayerLoan {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // player pubkey
+        32 + // player_mint pubkey
+        32 + // parent_team pubkey
+        32 + // parent_owner pubkey
+        32 + // borrower_team pubkey
+        1 + // position
+        8 + // fee
+        8 + // duration
+        1 + // status
+        8 + // created_at
+        9; // ends_at (Option<i64>)
+}
+
+// Loan status enum
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum LoanStatus {
+    Proposed,
+    Active,
+}
+
+// Context for a parent team proposing a loan
+#[derive(Accounts)]
+pub struct ProposeLoan<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        constraint = parent_team.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub parent_team: Account<'info, TeamAccount>,
+    
+    #[account(
+        constraint = borrower_team.key() != parent_team.key() @ ErrorCode::InvalidLoanTerms
+    )]
+    pub borrower_team: Account<'info, TeamAccount>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.team == Some(parent_team.key()) @ ErrorCode::PlayerNotOnTeam
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PlayerLoan::LEN,
+        seeds = [b"loan", player_account.key().as_ref()],
+        bump
+    )]
+    pub player_loan: Account<'info, PlayerLoan>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for a parent team withdrawing a loan that hasn't been accepted
+#[derive(Accounts)]
+pub struct CancelLoan<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"loan", player_loan.player.as_ref()],
+        bump,
+        constraint = player_loan.parent_owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_loan.status == LoanStatus::Proposed @ ErrorCode::LoanAlreadyActive
+    )]
+    pub player_loan: Account<'info, PlayerLoan>,
+}
+
+// Context for the borrowing team accepting a loan
+#[derive(Accounts)]
+pub struct AcceptLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"loan", player_account.key().as_ref()],
+        bump,
+        constraint = player_loan.status == LoanStatus::Proposed @ ErrorCode::LoanAlreadyActive
+    )]
+    pub player_loan: Account<'info, PlayerLoan>,
+    
+    #[account(
+        mut,
+        address = player_loan.borrower_team,
+        constraint = borrower_team.owner == borrower.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub borrower_team: Account<'info, TeamAccount>,
+    
+    #[account(address = player_loan.parent_team)]
+    pub parent_team: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        address = player_loan.player,
+        constraint = player_account.team == Some(player_loan.parent_team) @ ErrorCode::PlayerNotOnTeam
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the crank returning a player once the loan ends
+#[derive(Accounts)]
+pub struct ReturnLoanedPlayer<'info> {
+    pub cranker: Signer<'info>,
+    
+    // Closing pays the escrowed fee (and rent) to the parent owner
+    #[account(
+        mut,
+        close = parent_owner,
+        seeds = [b"loan", player_account.key().as_ref()],
+        bump,
+        constraint = player_loan.status == LoanStatus::Active @ ErrorCode::LoanNotActive
+    )]
+    pub player_loan: Account<'info, PlayerLoan>,
+    
+    #[account(
+        mut,
+        address = player_loan.borrower_team
+    )]
+    pub borrower_team: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        address = player_loan.parent_team
+    )]
+    pub parent_team: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        address = player_loan.player
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Receives the loan fee
+    #[account(
+        mut,
+        address = player_loan.parent_owner
+    )]
+    pub parent_owner: UncheckedAccount<'info>,
+}
+
+// Offer a rostered player to another team for a fixed period
+pub fn propose_loan(
+    ctx: Context<ProposeLoan>,
+    position: Position,
+    fee: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(
+        (MIN_LOAN_DURATION..=MAX_LOAN_DURATION).contains(&duration),
+        ErrorCode::InvalidLoanTerms
+    );
+    
+    let player_account = &ctx.accounts.player_account;
+    require!(
+        is_player_on_team(&ctx.accounts.parent_team, &player_account.mint),
+        ErrorCode::PlayerNotOnTeam
+    );
+    
+    let player_loan = &mut ctx.accounts.player_loan;
+    let clock = Clock::get()?;
+    
+    // A player's use rights can't be passed on beyond a rental
+    require!(player_account.active_user(clock.unix_timestamp).is_none(), ErrorCode::PlayerRented);
+    
+    player_loan.player = player_account.key();
+    player_loan.player_mint = player_account.mint;
+    player_loan.parent_team = ctx.accounts.parent_team.key();
+    player_loan.parent_owner = ctx.accounts.owner.key();
+    player_loan.borrower_team = ctx.accounts.borrower_team.key();
+    player_loan.position = position;
+    player_loan.fee = fee;
+    player_loan.duration = duration;
+    player_loan.status = LoanStatus::Proposed;
+    player_loan.created_at = clock.unix_timestamp;
+    player_loan.ends_at = None;
+    
+    Ok(())
+}
+
+pub fn cancel_loan(_ctx: Context<CancelLoan>) -> Result<()> {
+    Ok(())
+}
+
+// Accept a loan: the fee goes into escrow and the player joins the borrowing roster
+// (pass the borrowing team's current roster as remaining accounts)
+pub fn accept_loan(ctx: Context<AcceptLoan>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // The parent roster slot must still be there (it stays reserved during the loan)
+    require!(
+        is_player_on_team(&ctx.accounts.parent_team, &ctx.accounts.player_account.mint),
+        ErrorCode::PlayerNotOnTeam
+    );
+    
+    if ctx.accounts.player_loan.fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.borrower.to_account_info(),
+                    to: ctx.accounts.player_loan.to_account_info(),
+                },
+            ),
+            ctx.accounts.player_loan.fee,
+        )?;
+    }
+    
+    let player_loan = &mut ctx.accounts.player_loan;
+    let borrower_team = &mut ctx.accounts.borrower_team;
+    let player_account = &mut ctx.accounts.player_account;
+    
+    add_to_roster(borrower_team, player_account.mint, player_loan.position, clock.unix_timestamp)?;
+    borrower_team.last_updated = clock.unix_timestamp;
+    player_account.team = Some(borrower_team.key());
+    player_account.loaned_from = Some(player_loan.parent_team);
+    
+    let borrower_key = borrower_team.key();
+    refresh_roster_statistics(borrower_team, borrower_key, ctx.remaining_accounts, Some(player_account))?;
+    
+    player_loan.status = LoanStatus::Active;
+    player_loan.ends_at = Some(clock.unix_timestamp + player_loan.duration);
+    
+    msg!("{} loaned until {}", player_account.name, clock.unix_timestamp + player_loan.duration);
+    
+    Ok(())
+}
+
+// Send a loaned player back to the parent team once the loan has ended
+// (pass the rest of the borrowing team's roster, then the rest of the parent team's roster,
+// as remaining accounts)
+pub fn return_loaned_player(ctx: Context<ReturnLoanedPlayer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_loan = &ctx.accounts.player_loan;
+    
+    require!(
+        player_loan.ends_at.is_some_and(|ends_at| clock.unix_timestamp >= ends_at),
+        ErrorCode::LoanNotEnded
+    );
+    
+    let borrower_team = &mut ctx.accounts.borrower_team;
+    let player_account = &mut ctx.accounts.player_account;
+    
+    remove_from_roster(borrower_team, &player_account.mint)?;
+    borrower_team.last_updated = clock.unix_timestamp;
+    player_account.team = Some(player_loan.parent_team);
+    player_account.loaned_from = None;
+    
+    // The player's stats may have moved during the loan, so both teams are refreshed
+    require!(ctx.remaining_accounts.len() >= borrower_team.roster.len(), ErrorCode::InvalidRosterAccounts);
+    let (borrower_roster, parent_roster) = ctx.remaining_accounts.split_at(borrower_team.roster.len());
+    
+    let borrower_key = borrower_team.key();
+    refresh_roster_statistics(borrower_team, borrower_key, borrower_roster, None)?;
+    
+    let parent_team = &mut ctx.accounts.parent_team;
+    let parent_key = parent_team.key();
+    parent_team.last_updated = clock.unix_timestamp;
+    refresh_roster_statistics(parent_team, parent_key, parent_roster, Some(player_account))?;
+    
+    msg!("{} returned from loan", player_account.name);
+    
+    Ok(())
+}
@@ -0,0 +1,573 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::errors::ErrorCode;
+use crate::creator::CreatorAccount;
+use crate::player::PlayerAccount;
+use crate::utils::move_lamports;
+
+// Protocol cut of every sale, paid to the treasury
+pub const PROTOCOL_FEE_BPS: u64 = 250; // 2.5%
+
+// Basis point denominator for sale fees
+pub const FEE_DENOMINATOR: u64 = 10000;
+
+// Player Listing Structure (the NFT sits in an escrow token account owned by this PDA)
+#[account]
+pub struct PlayerListing {
+    pub seller: Pubkey,
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PlayerListing {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // seller pubkey
+        32 + // player pubkey
+        32 + // mint pubkey
+        8 + // price
+        8; // created_at
+}
+
+// Purchase Offer Structure (the offered lamports are held in this PDA)
+#[account]
+pub struct PurchaseOffer {
+    pub buyer: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PurchaseOffer {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // buyer pubkey
+        32 + // player pubkey
+        8 + // amount
+        8; // created_at
+}
+
+// How a sale price is divided up
+pub struct SaleSplit {
+    pub seller_proceeds: u64,
+    pub protocol_fee: u64,
+    pub royalty: u64,
+}
+
+// Context for listing a player for sale
+#[derive(Accounts)]
+pub struct ListPlayer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == seller.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(address = player_account.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = PlayerListing::LEN,
+        seeds = [b"listing", player_account.key().as_ref()],
+        bump
+    )]
+    pub player_listing: Account<'info, PlayerListing>,
+    
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"escrow", player_listing.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = player_listing
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for taking a player off the market
+#[derive(Accounts)]
+pub struct DelistPlayer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"listing", player_listing.player.as_ref()],
+        bump,
+        has_one = seller @ ErrorCode::UnauthorizedAccess
+    )]
+    pub player_listing: Account<'info, PlayerListing>,
+    
+    #[account(
+        mut,
+        address = player_listing.player
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", player_listing.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = player_listing.mint,
+        token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Context for buying a listed player at the asking price
+#[derive(Accounts)]
+pub struct BuyPlayer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"listing", player_account.key().as_ref()],
+        bump,
+        has_one = seller @ ErrorCode::UnauthorizedAccess,
+        constraint = player_listing.seller != buyer.key() @ ErrorCode::InvalidSale
+    )]
+    pub player_listing: Account<'info, PlayerListing>,
+    
+    // Escrowed players can't be rostered or rented, so a listed player always sells
+    #[account(
+        mut,
+        address = player_listing.player,
+        constraint = player_account.owner == player_listing.seller @ ErrorCode::UnauthorizedAccess
+    )]
+    pub player_account: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", player_listing.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = player_listing.mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Seller, receives the proceeds and the listing rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Required when the player was minted by a creator
+    pub creator_account: Option<Account<'info, CreatorAccount>>,
+    
+    /// CHECK: Creator authority, receives the royalty; checked against creator_account
+    #[account(mut)]
+    pub creator_authority: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for making an offer on a player, listed or not
+#[derive(Accounts)]
+pub struct MakePurchaseOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner != buyer.key() @ ErrorCode::InvalidSale
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = PurchaseOffer::LEN,
+        seeds = [b"purchase_offer", player_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_offer: Account<'info, PurchaseOffer>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for a buyer withdrawing an offer (escrowed lamports come back with the rent)
+#[derive(Accounts)]
+pub struct CancelPurchaseOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"purchase_offer", purchase_offer.player.as_ref(), buyer.key().as_ref()],
+        bump,
+        has_one = buyer @ ErrorCode::UnauthorizedAccess
+    )]
+    pub purchase_offer: Account<'info, PurchaseOffer>,
+}
+
+// Context for the owner accepting an offer (the NFT must be in the owner's wallet, not listed)
+#[derive(Accounts)]
+pub struct AcceptPurchaseOffer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == seller.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Box<Account<'info, PlayerAccount>>,
+    
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"purchase_offer", player_account.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        has_one = buyer @ ErrorCode::UnauthorizedAccess
+    )]
+    pub purchase_offer: Account<'info, PurchaseOffer>,
+    
+    /// CHECK: Buyer, gets the offer rent back
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = player_account.mint,
+        token::authority = seller
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = player_account.mint,
+        token::authority = buyer
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Required when the player was minted by a creator
+    pub creator_account: Option<Account<'info, CreatorAccount>>,
+    
+    /// CHECK: Creator authority, receives the royalty; checked against creator_account
+    #[account(mut)]
+    pub creator_authority: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Divide a sale price between seller, treasury and creator
+pub fn split_sale(price: u64, royalty_bps: u16) -> SaleSplit {
+    let protocol_fee = (price as u128 * PROTOCOL_FEE_BPS as u128 / FEE_DENOMINATOR as u128) as u64;
+    let royalty = (price as u128 * royalty_bps as u128 / FEE_DENOMINATOR as u128) as u64;
+    
+    SaleSplit {
+        seller_proceeds: price - protocol_fee - royalty,
+        protocol_fee,
+        royalty,
+    }
+}
+
+// Resolve the royalty owed on a player, checking the creator accounts match the player's creator
+pub fn creator_royalty<'info>(
+    player_account: &PlayerAccount,
+    creator_account: Option<&Account<'info, CreatorAccount>>,
+    creator_authority: Option<&UncheckedAccount<'info>>,
+) -> Result<Option<(u16, AccountInfo<'info>)>> {
+    let Some(creator) = player_account.creator else {
+        return Ok(None);
+    };
+    
+    let (Some(creator_account), Some(creator_authority)) = (creator_account, creator_authority) else {
+        return Err(ErrorCode::InvalidCreatorAccount.into());
+    };
+    
+    require_keys_eq!(creator_account.key(), creator, ErrorCode::InvalidCreatorAccount);
+    require_keys_eq!(creator_authority.key(), creator_account.authority, ErrorCode::InvalidCreatorAccount);
+    
+    Ok(Some((creator_account.creator_fee_basis_points, creator_authority.to_account_info())))
+}
+
+// Put a player up for sale; the NFT moves into escrow until sold or delisted
+pub fn list_player(ctx: Context<ListPlayer>, price: u64) -> Result<()> {
+    require!(price > 0, ErrorCode::InvalidSale);
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    let player_listing = &mut ctx.accounts.player_listing;
+    let clock = Clock::get()?;
+    
+    player_listing.seller = ctx.accounts.seller.key();
+    player_listing.player = ctx.accounts.player_account.key();
+    player_listing.mint = ctx.accounts.player_account.mint;
+    player_listing.price = price;
+    player_listing.created_at = clock.unix_timestamp;
+    ctx.accounts.player_account.escrowed = true;
+    
+    msg!("{} listed for {} lamports", ctx.accounts.player_account.name, price);
+    
+    Ok(())
+}
+
+// Return the NFT from escrow to the seller
+pub fn delist_player(ctx: Context<DelistPlayer>) -> Result<()> {
+    ctx.accounts.player_account.escrowed = false;
+    
+    release_listing_escrow(
+        &ctx.accounts.player_listing,
+        ctx.bumps.player_listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.seller_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )
+}
+
+// Buy a listed player at the asking price
+pub fn buy_player(ctx: Context<BuyPlayer>) -> Result<()> {
+    let price = ctx.accounts.player_listing.price;
+    let royalty = creator_royalty(
+        &ctx.accounts.player_account,
+        ctx.accounts.creator_account.as_ref(),
+        ctx.accounts.creator_authority.as_ref(),
+    )?;
+    let split = split_sale(price, royalty.as_ref().map_or(0, |(bps, _)| *bps));
+    
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    pay_from_wallet(&system_program, &buyer, &ctx.accounts.seller.to_account_info(), split.seller_proceeds)?;
+    pay_from_wallet(&system_program, &buyer, &ctx.accounts.treasury.to_account_info(), split.protocol_fee)?;
+    if let Some((_, creator_authority)) = royalty {
+        pay_from_wallet(&system_program, &buyer, &creator_authority, split.royalty)?;
+    }
+    
+    release_listing_escrow(
+        &ctx.accounts.player_listing,
+        ctx.bumps.player_listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.buyer_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let player_account = &mut ctx.accounts.player_account;
+    player_account.owner = ctx.accounts.buyer.key();
+    player_account.escrowed = false;
+    
+    msg!("{} sold for {} lamports", player_account.name, price);
+    
+    Ok(())
+}
+
+// Offer lamports for a player; the amount is escrowed until accepted or withdrawn
+pub fn make_purchase_offer(ctx: Context<MakePurchaseOffer>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidSale);
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.purchase_offer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let purchase_offer = &mut ctx.accounts.purchase_offer;
+    let clock = Clock::get()?;
+    
+    purchase_offer.buyer = ctx.accounts.buyer.key();
+    purchase_offer.player = ctx.accounts.player_account.key();
+    purchase_offer.amount = amount;
+    purchase_offer.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+pub fn cancel_purchase_offer(_ctx: Context<CancelPurchaseOffer>) -> Result<()> {
+    Ok(())
+}
+
+// Accept an offer: the NFT goes to the buyer and the escrowed lamports are paid out
+pub fn accept_purchase_offer(ctx: Context<AcceptPurchaseOffer>) -> Result<()> {
+    let amount = ctx.accounts.purchase_offer.amount;
+    let royalty = creator_royalty(
+        &ctx.accounts.player_account,
+        ctx.accounts.creator_account.as_ref(),
+        ctx.accounts.creator_authority.as_ref(),
+    )?;
+    let split = split_sale(amount, royalty.as_ref().map_or(0, |(bps, _)| *bps));
+    
+    let offer_info = ctx.accounts.purchase_offer.to_account_info();
+    move_lamports(&offer_info, &ctx.accounts.seller.to_account_info(), split.seller_proceeds)?;
+    move_lamports(&offer_info, &ctx.accounts.treasury.to_account_info(), split.protocol_fee)?;
+    if let Some((_, creator_authority)) = royalty {
+        move_lamports(&offer_info, &creator_authority, split.royalty)?;
+    }
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    let player_account = &mut ctx.accounts.player_account;
+    player_account.owner = ctx.accounts.buyer.key();
+    
+    msg!("{} sold for {} lamports", player_account.name, amount);
+    
+    Ok(())
+}
+
+// Pay a share of a sale from a signing wallet
+pub fn pay_from_wallet<'info>(
+    system_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: from.clone(),
+                to: to.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+// Send an escrowed NFT out and close the escrow token account; `authority` is the PDA owning the escrow
+pub fn release_escrow<'info>(
+    authority: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    escrow_token_account: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow_token_account.to_account_info(),
+                to: destination.clone(),
+                authority: authority.clone(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+    
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: rent_receiver.clone(),
+            authority: authority.clone(),
+        },
+        signer,
+    ))
+}
+
+// Helper to release a listing's escrow, signing as the listing PDA
+fn release_listing_escrow<'info>(
+    player_listing: &Account<'info, PlayerListing>,
+    listing_bump: u8,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        b"listing".as_ref(),
+        player_listing.player.as_ref(),
+        &[listing_bump],
+    ];
+    
+    release_escrow(
+        &player_listing.to_account_info(),
+        &[&seeds[..]],
+        escrow_token_account,
+        destination,
+        rent_receiver,
+        token_program,
+    )
+}
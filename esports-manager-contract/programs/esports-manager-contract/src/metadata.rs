@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::accounts::Metadata;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, update_metadata_accounts_v2, CreateMetadataAccountsV3, UpdateMetadataAccountsV2,
+};
+use crate::errors::ErrorCode;
+use crate::player::PlayerAccount;
+
+// Minimum time between owner-requested metadata refreshes
+pub const METADATA_REFRESH_COOLDOWN: i64 = 3600;
+
+// Symbol shared by every player NFT (matches the metadata server default)
+pub const PLAYER_METADATA_SYMBOL: &str = "5VS5";
+
+// Context for an owner refreshing a player's NFT metadata
+#[derive(Accounts)]
+pub struct RefreshPlayerMetadata<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = player_account.has_metadata @ ErrorCode::InvalidMetadataAccount
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Metaplex metadata for the player mint, verified in sync_player_metadata
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+// Metadata URI for the player's current revision (the revision busts marketplace caches)
+pub fn player_metadata_uri(player: &PlayerAccount) -> String {
+    format!("{}?rev={}", player.uri, player.metadata_revision)
+}
+
+// Accounts for the Metaplex CPI that creates a player's metadata
+pub struct PlayerMetadataAccounts<'info> {
+    pub metadata: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+// Create the Metaplex metadata for a freshly minted player
+// The player PDA becomes the update authority so progression can be synced on-chain
+pub fn create_player_metadata<'info>(
+    player_account: &mut Account<'info, PlayerAccount>,
+    accounts: PlayerMetadataAccounts<'info>,
+) -> Result<()> {
+    require_keys_eq!(
+        accounts.metadata.key(),
+        Metadata::find_pda(&player_account.mint).0,
+        ErrorCode::InvalidMetadataAccount
+    );
+    
+    let data = DataV2 {
+        name: player_account.name.clone(),
+        symbol: PLAYER_METADATA_SYMBOL.to_string(),
+        uri: player_metadata_uri(player_account),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    
+    create_metadata_accounts_v3(
+        CpiContext::new(
+            accounts.token_metadata_program,
+            CreateMetadataAccountsV3 {
+                metadata: accounts.metadata,
+                mint: accounts.mint,
+                mint_authority: accounts.mint_authority,
+                payer: accounts.payer,
+                update_authority: player_account.to_account_info(),
+                system_program: accounts.system_program,
+                rent: accounts.rent,
+            },
+        ),
+        data,
+        true,
+        false,
+        None,
+    )?;
+    
+    player_account.has_metadata = true;
+    
+    Ok(())
+}
+
+// Point the Metaplex metadata at the player's current revision
+// The player PDA must be the metadata update authority and signs the CPI
+pub fn sync_player_metadata<'info>(
+    player_account: &Account<'info, PlayerAccount>,
+    player_bump: u8,
+    metadata: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(
+        metadata.key(),
+        Metadata::find_pda(&player_account.mint).0,
+        ErrorCode::InvalidMetadataAccount
+    );
+    
+    let current = {
+        let data = metadata.try_borrow_data()?;
+        Metadata::from_bytes(&data).map_err(|_| ErrorCode::InvalidMetadataAccount)?
+    };
+    
+    require_keys_eq!(
+        current.update_authority,
+        player_account.key(),
+        ErrorCode::InvalidMetadataAccount
+    );
+    
+    // Keep everything but the URI as it is (legacy metadata strings are null-padded)
+    let data = DataV2 {
+        name: current.name.trim_end_matches('\0').to_string(),
+        symbol: current.symbol.trim_end_matches('\0').to_string(),
+        uri: player_metadata_uri(player_account),
+        seller_fee_basis_points: current.seller_fee_basis_points,
+        creators: current.creators,
+        collection: current.collection,
+        uses: current.uses,
+    };
+    
+    let player_info = player_account.to_account_info();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"player", player_account.mint.as_ref(), &[player_bump]]];
+    
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            token_metadata_program.clone(),
+            UpdateMetadataAccountsV2 {
+                metadata: metadata.clone(),
+                update_authority: player_info,
+            },
+            signer_seeds,
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )?;
+    
+    Ok(())
+}
+
+// Sync metadata for players that have it; the metadata accounts can't be skipped then
+pub fn sync_player_metadata_if_minted<'info>(
+    player_account: &Account<'info, PlayerAccount>,
+    player_bump: u8,
+    metadata: &Option<UncheckedAccount<'info>>,
+    token_metadata_program: &Option<UncheckedAccount<'info>>,
+) -> Result<()> {
+    if !player_account.has_metadata {
+        return Ok(());
+    }
+    
+    match (metadata, token_metadata_program) {
+        (Some(metadata), Some(program)) => {
+            sync_player_metadata(player_account, player_bump, metadata, program)
+        }
+        _ => err!(ErrorCode::MetadataAccountsRequired),
+    }
+}
+
+// Bump the metadata revision and push it to Metaplex (rate-limited)
+pub fn refresh_player_metadata(ctx: Context<RefreshPlayerMetadata>) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.unix_timestamp - player_account.last_metadata_refresh >= METADATA_REFRESH_COOLDOWN,
+        ErrorCode::MetadataRefreshCooldown
+    );
+    
+    player_account.metadata_revision += 1;
+    player_account.last_metadata_refresh = clock.unix_timestamp;
+    
+    sync_player_metadata(
+        &ctx.accounts.player_account,
+        ctx.bumps.player_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+    )
+}
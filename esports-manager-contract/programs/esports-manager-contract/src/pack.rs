@@ -0,0 +1,430 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_lang::system_program;
+use anchor_spl::token::{Mint, Token};
+use crate::config::ProgramConfig;
+use crate::errors::ErrorCode;
+use crate::player::{PlayerAccount, Position, Rarity};
+
+// Most players a single pack can hold (bounded by compute per transaction)
+pub const MAX_PACK_SIZE: u8 = 5;
+
+// Rarity odds are expressed in basis points and must add up to this
+pub const ODDS_TOTAL_BPS: u16 = 10_000;
+
+// Pack Definition Structure (admin-managed product in the store)
+#[account]
+pub struct PackDefinition {
+    pub authority: Pubkey,
+    pub pack_id: u32,
+    pub name: String,
+    pub price: u64,
+    pub players_per_pack: u8,
+    pub rarity_odds: [u16; 5], // Basis points per tier, Common through Legendary
+    pub active: bool,
+    pub packs_opened: u32,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PackDefinition {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // authority pubkey
+        4 + // pack_id
+        36 + // name (max 32 chars)
+        8 + // price
+        1 + // players_per_pack
+        10 + // rarity_odds
+        1 + // active
+        4 + // packs_opened
+        8; // created_at
+}
+
+// Pack Opening Structure (a paid pack waiting for its rolls to be revealed)
+#[account]
+pub struct PackOpening {
+    pub buyer: Pubkey,
+    pub pack: Pubkey,
+    pub players_per_pack: u8,
+    pub rarity_odds: [u16; 5], // Odds at purchase time
+    pub first_serial: u64,
+    pub commit_slot: u64, // Rolls are seeded by this slot's hash, unknown when buying
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PackOpening {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // buyer pubkey
+        32 + // pack pubkey
+        1 + // players_per_pack
+        10 + // rarity_odds
+        8 + // first_serial
+        8 + // commit_slot
+        8; // created_at
+}
+
+// Map a roll in 0..ODDS_TOTAL_BPS onto a tier
+pub fn rarity_for_roll(rarity_odds: &[u16; 5], roll: u16) -> Rarity {
+    let tiers = [Rarity::Common, Rarity::Uncommon, Rarity::Rare, Rarity::Epic, Rarity::Legendary];
+    let mut cumulative = 0;
+    for (tier, odds) in tiers.iter().zip(rarity_odds.iter()) {
+        cumulative += odds;
+        if roll < cumulative {
+            return *tier;
+        }
+    }
+    Rarity::Common
+}
+
+// Starting stat and potential ranges (inclusive) for a pack player of each tier
+pub fn pack_ranges(rarity: Rarity) -> ((u8, u8), (u8, u8)) {
+    match rarity {
+        Rarity::Common => ((40, 60), (50, 59)),
+        Rarity::Uncommon => ((50, 70), (60, 69)),
+        Rarity::Rare => ((60, 80), (70, 79)),
+        Rarity::Epic => ((70, 88), (80, 89)),
+        Rarity::Legendary => ((80, 95), (90, 100)),
+    }
+}
+
+// Context for creating a pack definition
+#[derive(Accounts)]
+#[instruction(pack_id: u32)]
+pub struct CreatePackDefinition<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PackDefinition::LEN,
+        seeds = [b"pack", pack_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pack_definition: Account<'info, PackDefinition>,
+    
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Context for updating a pack definition
+#[derive(Accounts)]
+pub struct UpdatePackDefinition<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"pack", pack_definition.pack_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub pack_definition: Account<'info, PackDefinition>,
+}
+
+// Context for buying a pack
+#[derive(Accounts)]
+pub struct OpenPack<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pack", pack_definition.pack_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = pack_definition.active @ ErrorCode::PackNotAvailable
+    )]
+    pub pack_definition: Account<'info, PackDefinition>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = PackOpening::LEN,
+        seeds = [
+            b"pack_opening",
+            pack_definition.key().as_ref(),
+            pack_definition.packs_opened.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub pack_opening: Account<'info, PackOpening>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for minting the players of a bought pack
+// Remaining accounts: (mint, player PDA) pairs, one per player in the pack
+#[derive(Accounts)]
+pub struct RevealPack<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = buyer,
+        has_one = buyer @ ErrorCode::UnauthorizedAccess
+    )]
+    pub pack_opening: Account<'info, PackOpening>,
+    
+    /// CHECK: SlotHashes sysvar, read by hand (too large to deserialize)
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Create a new pack product
+pub fn create_pack_definition(
+    ctx: Context<CreatePackDefinition>,
+    pack_id: u32,
+    name: String,
+    price: u64,
+    players_per_pack: u8,
+    rarity_odds: [u16; 5],
+) -> Result<()> {
+    validate_pack(&name, players_per_pack, &rarity_odds)?;
+    
+    let pack_definition = &mut ctx.accounts.pack_definition;
+    let clock = Clock::get()?;
+    
+    pack_definition.authority = ctx.accounts.authority.key();
+    pack_definition.pack_id = pack_id;
+    pack_definition.name = name;
+    pack_definition.price = price;
+    pack_definition.players_per_pack = players_per_pack;
+    pack_definition.rarity_odds = rarity_odds;
+    pack_definition.active = true;
+    pack_definition.packs_opened = 0;
+    pack_definition.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+// Update price, contents or availability of a pack (the id is fixed by the PDA)
+pub fn update_pack_definition(
+    ctx: Context<UpdatePackDefinition>,
+    name: String,
+    price: u64,
+    players_per_pack: u8,
+    rarity_odds: [u16; 5],
+    active: bool,
+) -> Result<()> {
+    validate_pack(&name, players_per_pack, &rarity_odds)?;
+    
+    let pack_definition = &mut ctx.accounts.pack_definition;
+    
+    pack_definition.name = name;
+    pack_definition.price = price;
+    pack_definition.players_per_pack = players_per_pack;
+    pack_definition.rarity_odds = rarity_odds;
+    pack_definition.active = active;
+    
+    Ok(())
+}
+
+// Pay for a pack; the players are rolled and minted by reveal_pack from a later slot
+pub fn open_pack(ctx: Context<OpenPack>) -> Result<()> {
+    // Pack revenue goes to the protocol treasury
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        ),
+        ctx.accounts.pack_definition.price,
+    )?;
+    
+    let clock = Clock::get()?;
+    let pack_definition = &mut ctx.accounts.pack_definition;
+    let pack_opening = &mut ctx.accounts.pack_opening;
+    
+    pack_opening.buyer = ctx.accounts.buyer.key();
+    pack_opening.pack = pack_definition.key();
+    pack_opening.players_per_pack = pack_definition.players_per_pack;
+    pack_opening.rarity_odds = pack_definition.rarity_odds;
+    pack_opening.first_serial = pack_definition.packs_opened as u64 * pack_definition.players_per_pack as u64 + 1;
+    pack_opening.commit_slot = clock.slot;
+    pack_opening.created_at = clock.unix_timestamp;
+    
+    pack_definition.packs_opened += 1;
+    
+    Ok(())
+}
+
+// Mint the players of a bought pack, rolling each from the hash of the purchase slot
+pub fn reveal_pack<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevealPack<'info>>,
+    uris: Vec<String>,
+) -> Result<()> {
+    let pack_opening = &ctx.accounts.pack_opening;
+    let players_per_pack = pack_opening.players_per_pack as usize;
+    
+    require!(
+        uris.len() == players_per_pack && ctx.remaining_accounts.len() == players_per_pack * 2,
+        ErrorCode::InvalidPackAccounts
+    );
+    
+    let clock = Clock::get()?;
+    require!(clock.slot > pack_opening.commit_slot, ErrorCode::PackNotRevealable);
+    
+    // Once the purchase slot drops out of SlotHashes the pack falls back to the floor of
+    // every range, so sitting on an unwanted result never pays off
+    let entropy = find_slot_hash(&ctx.accounts.slot_hashes, pack_opening.commit_slot)?
+        .map(|slot_hash| hashv(&[slot_hash.as_ref(), pack_opening.key().as_ref()]).to_bytes());
+    
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let space = PlayerAccount::LEN;
+    let lamports = Rent::get()?.minimum_balance(space);
+    
+    for (i, (accounts, uri)) in ctx.remaining_accounts.chunks(2).zip(uris).enumerate() {
+        let mint_info = &accounts[0];
+        let player_info = &accounts[1];
+        
+        // The mint must be a real SPL mint and the player PDA must not exist yet
+        let mint = Account::<Mint>::try_from(mint_info)?;
+        let (player_address, bump) = Pubkey::find_program_address(
+            &[b"player", mint.key().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(player_info.key(), player_address, ErrorCode::InvalidPackAccounts);
+        require!(player_info.lamports() == 0, ErrorCode::InvalidPackAccounts);
+        
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: buyer.clone(),
+                    to: player_info.clone(),
+                },
+                &[&[b"player", mint.key().as_ref(), &[bump]]],
+            ),
+            lamports,
+            space as u64,
+            ctx.program_id,
+        )?;
+        
+        let serial = pack_opening.first_serial + i as u64;
+        let player = roll_pack_player(pack_opening, entropy, &clock, mint.key(), serial, uri);
+        
+        let mut data = player_info.try_borrow_mut_data()?;
+        player.try_serialize(&mut &mut data[..])?;
+        
+        msg!("Pack player {} pulled ({}-{} potential)", player.name, player.potential_min, player.potential_max);
+    }
+    
+    Ok(())
+}
+
+// Look up a slot's hash in the SlotHashes sysvar (entries are newest first)
+pub fn find_slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>> {
+    const ENTRY_LEN: usize = 8 + 32;
+    
+    let data = slot_hashes.try_borrow_data()?;
+    let count = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+    
+    for entry in data[8..].chunks_exact(ENTRY_LEN).take(count) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot == slot {
+            return Ok(Some(entry[8..].try_into().unwrap()));
+        }
+        if entry_slot < slot {
+            break;
+        }
+    }
+    
+    Ok(None)
+}
+
+// Roll derived from committed slot-hash entropy; `salt` keeps rolls for different items distinct
+// Without entropy (the slot hash expired) every roll is zero, the floor of its range
+pub fn slot_hash_roll(entropy: Option<[u8; 32]>, salt: &[u8], seed: u8) -> u64 {
+    match entropy {
+        Some(entropy) => {
+            let hash = hashv(&[entropy.as_ref(), salt, &[seed]]).to_bytes();
+            u64::from_le_bytes(hash[..8].try_into().unwrap())
+        }
+        None => 0,
+    }
+}
+
+// Build a fresh player whose rarity is rolled from the pack odds
+fn roll_pack_player(
+    pack_opening: &PackOpening,
+    entropy: Option<[u8; 32]>,
+    clock: &Clock,
+    mint: Pubkey,
+    serial: u64,
+    uri: String,
+) -> PlayerAccount {
+    // The serial keeps players from the same pack distinct; the caller-chosen mint is left out
+    let roll = |seed: u8| slot_hash_roll(entropy, &serial.to_le_bytes(), seed);
+    let in_range = |seed: u8, (low, high): (u8, u8)| low + (roll(seed) % (high - low + 1) as u64) as u8;
+    
+    let rarity = rarity_for_roll(&pack_opening.rarity_odds, (roll(50) % ODDS_TOTAL_BPS as u64) as u16);
+    let (stat_range, potential_range) = pack_ranges(rarity);
+    let positions = [Position::Top, Position::Jungle, Position::Mid, Position::Adc, Position::Support];
+    let starting_age = 16 + (roll(57) % 7) as u8;
+    
+    let mut player = PlayerAccount::new(
+        pack_opening.buyer,
+        mint,
+        format!("Prospect #{}", serial),
+        positions[(roll(51) % positions.len() as u64) as usize],
+        uri,
+        starting_age,
+        clock.unix_timestamp,
+    );
+    player.mechanical = in_range(52, stat_range);
+    player.game_knowledge = in_range(53, stat_range);
+    player.team_communication = in_range(54, stat_range);
+    player.adaptability = in_range(55, stat_range);
+    player.consistency = in_range(56, stat_range);
+    // Pack pulls only show their tier's potential band; a reporter seals the true value inside it
+    player.set_minted_potential(rarity, potential_range);
+    
+    player
+}
+
+// Helper to validate pack parameters
+fn validate_pack(name: &str, players_per_pack: u8, rarity_odds: &[u16; 5]) -> Result<()> {
+    require!(name.len() <= 32, ErrorCode::InvalidPackDefinition);
+    require!(
+        players_per_pack > 0 && players_per_pack <= MAX_PACK_SIZE,
+        ErrorCode::InvalidPackDefinition
+    );
+    require!(
+        rarity_odds.iter().map(|odds| *odds as u32).sum::<u32>() == ODDS_TOTAL_BPS as u32,
+        ErrorCode::InvalidPackDefinition
+    );
+    
+    Ok(())
+}
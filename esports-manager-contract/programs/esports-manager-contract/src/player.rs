@@ -1,7 +1,55 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_lang::{system_program, Discriminator};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
 use crate::errors::ErrorCode;
-use crate::utils::{get_random_value, safe_update_stat};
+use crate::creator::CreatorAccount;
+use crate::ability::{
+    AbilityDefinition, CLUTCH_FACTOR_ID, CREATOR_SIGNATURE_ID, PERFECT_EXECUTION_ID, SHOT_CALLER_ID,
+    STARTING_ABILITY_LEVEL,
+};
+use crate::team::legacy_position;
+use crate::reporter::ReporterAccount;
+use crate::metadata::{create_player_metadata, sync_player_metadata_if_minted, PlayerMetadataAccounts};
+use crate::utils::{get_random_value, safe_update_stat, SEASONS_PER_YEAR, SEASON_DURATION};
+
+// Age model: a player ages one year per competitive year (SEASONS_PER_YEAR seasons)
+pub const PEAK_AGE_START: u8 = 22;
+pub const PEAK_AGE_END: u8 = 26;
+pub const MAX_PLAYING_AGE: u8 = 35;
+
+// Form model: form drifts toward the baseline over time and dips with back-to-back matches
+pub const FORM_BASELINE: u8 = 70;
+pub const FORM_DRIFT_PER_DAY: i64 = 4;
+pub const FORM_REST_PERIOD: i64 = 12 * 3600;
+pub const BACK_TO_BACK_FORM_PENALTY: i8 = -5;
+
+// Morale model: 50 is neutral, every 10 points away shifts effective stats by 1
+pub const MORALE_BASELINE: u8 = 50;
+pub const MORALE_WIN: i8 = 3;
+pub const MORALE_LOSS: i8 = -2;
+pub const MORALE_ROSTERED: i8 = 5;
+pub const MORALE_BENCHED: i8 = -10;
+pub const MORALE_PAID: i8 = 1;
+pub const MORALE_UNPAID: i8 = -5; // Per salary period paid late
+
+// Injury chances (percent) and duration range
+pub const MATCH_INJURY_CHANCE: u64 = 3;
+pub const FATIGUED_MATCH_INJURY_CHANCE: u64 = 8;
+pub const BOOTCAMP_INJURY_CHANCE: u64 = 5;
+pub const MAX_INJURY_DAYS: u64 = 5;
+
+// Width of the public range a minted player's potential lies in
+pub const POTENTIAL_RANGE_WIDTH: u8 = 10;
+
+// Most heroes tracked per player; the least played hero is dropped when full
+pub const MAX_HERO_POOL: usize = 12;
+pub const STARTING_HERO_MASTERY: u8 = 10;
+
+// Most special abilities a player can hold (the account reserves room for this many)
+pub const MAX_SPECIAL_ABILITIES: usize = 4;
+
+// Size of player accounts created before the current layout
+pub const LEGACY_PLAYER_ACCOUNT_LEN: usize = 543;
 
 // Player Account Structure
 #[account]
@@ -9,7 +57,7 @@ pub struct PlayerAccount {
     pub owner: Pubkey,
     pub mint: Pubkey,
     pub name: String,
-    pub position: String,
+    pub position: Position,
     pub created_at: i64,
     pub last_updated: i64,
     pub team: Option<Pubkey>,
@@ -25,8 +73,8 @@ pub struct PlayerAccount {
     // Special abilities
     pub special_abilities: Vec<SpecialAbility>,
     
-    // Game-specific data (e.g., champion proficiencies in MOBA)
-    pub game_specific_data: Vec<u8>,
+    // Game-specific data (hero/champion proficiencies in MOBA)
+    pub game_specific_data: GameSpecificData,
     
     // Performance metrics
     pub experience: u32,
@@ -34,8 +82,16 @@ pub struct PlayerAccount {
     pub wins: u32,
     pub mvp_count: u32,
     pub form: u8,
-    pub potential: u8,
+    pub potential: u8, // Zero until revealed; the true value only lives in the commitment
     pub rarity: Rarity,
+    pub minted_rarity: Rarity, // Exclusive athletes never drop below this tier
+    
+    // Hidden potential: only the range and the commitment are public until it is opened
+    pub potential_min: u8,
+    pub potential_max: u8,
+    pub potential_commitment: Option<[u8; 32]>,
+    pub potential_revealed: bool,
+    pub potential_growth: u8, // Potential gained since the commitment was made (while hidden)
     
     // Creator information (for exclusive athletes)
     pub creator: Option<Pubkey>,
@@ -43,6 +99,41 @@ pub struct PlayerAccount {
     
     // Match history (recent matches only, full history stored off-chain)
     pub performance_history: Vec<MatchPerformance>,
+    
+    // Multi-day training program the player is locked into (if any)
+    pub active_training: Option<ActiveTraining>,
+    
+    // Season in which abilities were last respecced (one respec per season)
+    pub last_respec_season: Option<u32>,
+    
+    // Age and retirement (retired players are frozen in the Hall of Fame)
+    pub starting_age: u8,
+    pub last_aged_at: u8, // Age up to which post-peak decay has been applied
+    pub retired: bool,
+    pub retired_at: Option<i64>,
+    
+    // Timestamps backing the lazily computed form
+    pub form_updated_at: i64,
+    pub last_match_at: i64,
+    
+    // Availability and mood
+    pub injured_until: i64,
+    pub morale: u8,
+    
+    // Metaplex metadata sync (the revision is bumped on every progression event)
+    pub metadata_revision: u32,
+    pub last_metadata_refresh: i64,
+    pub has_metadata: bool, // Metaplex metadata exists with the player PDA as update authority
+    
+    // Rented use rights: the user can roster and play the athlete until user_expires
+    pub user: Option<Pubkey>,
+    pub user_expires: i64,
+    
+    // Parent team while out on loan (the player keeps that roster slot)
+    pub loaned_from: Option<Pubkey>,
+    
+    // NFT is escrowed by a listing, auction or trade: no rostering, renting or training until released
+    pub escrowed: bool,
 }
 
 // Fixed size for account allocation
@@ -52,7 +143,7 @@ impl PlayerAccount {
         32 + // owner pubkey
         32 + // mint pubkey
         36 + // name (max 32 chars)
-        36 + // position (max 32 chars)
+        1 + // position
         8 + // created_at
         8 + // last_updated
         33 + // team (Option<Pubkey>)
@@ -62,8 +153,8 @@ impl PlayerAccount {
         1 + // team_communication
         1 + // adaptability
         1 + // consistency
-        64 + // special_abilities (variable size, estimate)
-        64 + // game_specific_data (variable size, estimate)
+        4 + SpecialAbility::LEN * MAX_SPECIAL_ABILITIES + // special_abilities
+        64 + // game_specific_data (up to MAX_HERO_POOL heroes)
         4 + // experience
         4 + // matches_played
         4 + // wins
@@ -71,18 +162,358 @@ impl PlayerAccount {
         1 + // form
         1 + // potential
         1 + // rarity
+        1 + // minted_rarity
+        1 + // potential_min
+        1 + // potential_max
+        33 + // potential_commitment (Option<[u8; 32]>)
+        1 + // potential_revealed
+        1 + // potential_growth
         1 + // is_exclusive
         33 + // creator (Option<Pubkey>)
-        128; // performance_history (variable size, estimate)
+        128 + // performance_history (variable size, estimate)
+        18 + // active_training (Option<ActiveTraining>)
+        5 + // last_respec_season (Option<u32>)
+        1 + // starting_age
+        1 + // last_aged_at
+        1 + // retired
+        9 + // retired_at (Option<i64>)
+        8 + // form_updated_at
+        8 + // last_match_at
+        8 + // injured_until
+        1 + // morale
+        4 + // metadata_revision
+        8 + // last_metadata_refresh
+        1 + // has_metadata
+        33 + // user (Option<Pubkey>)
+        8 + // user_expires
+        33 + // loaned_from (Option<Pubkey>)
+        1; // escrowed
+    
+    // A freshly minted player: every career, market and lifecycle field at its starting
+    // value. Callers roll the stats, abilities and heroes, then set the rarity and the
+    // public potential range; new fields only need a default here
+    pub fn new(
+        owner: Pubkey,
+        mint: Pubkey,
+        name: String,
+        position: Position,
+        uri: String,
+        starting_age: u8,
+        now: i64,
+    ) -> Self {
+        Self {
+            owner,
+            mint,
+            name,
+            position,
+            created_at: now,
+            last_updated: now,
+            team: None,
+            uri,
+            mechanical: 0,
+            game_knowledge: 0,
+            team_communication: 0,
+            adaptability: 0,
+            consistency: 0,
+            special_abilities: Vec::new(),
+            game_specific_data: GameSpecificData::default(),
+            experience: 0,
+            matches_played: 0,
+            wins: 0,
+            mvp_count: 0,
+            form: FORM_BASELINE,
+            potential: 0,
+            rarity: Rarity::Common,
+            minted_rarity: Rarity::Common,
+            potential_min: 0,
+            potential_max: 0,
+            potential_commitment: None,
+            potential_revealed: false,
+            potential_growth: 0,
+            creator: None,
+            is_exclusive: false,
+            performance_history: Vec::new(),
+            active_training: None,
+            last_respec_season: None,
+            starting_age,
+            last_aged_at: starting_age,
+            retired: false,
+            retired_at: None,
+            form_updated_at: now,
+            last_match_at: 0,
+            injured_until: 0,
+            morale: MORALE_BASELINE,
+            metadata_revision: 0,
+            last_metadata_refresh: 0,
+            has_metadata: false,
+            user: None,
+            user_expires: 0,
+            loaned_from: None,
+            escrowed: false,
+        }
+    }
+    
+    // Set the minted tier (the floor for exclusive athletes) and the public potential range;
+    // a reporter picks and seals the true value inside it (seal_potential in scouting.rs)
+    pub fn set_minted_potential(&mut self, rarity: Rarity, range: (u8, u8)) {
+        self.rarity = rarity;
+        self.minted_rarity = rarity;
+        self.potential = 0;
+        self.potential_min = range.0;
+        self.potential_max = range.1;
+        self.potential_commitment = None;
+        self.potential_revealed = false;
+        self.potential_growth = 0;
+    }
+    
+    // A player locked into a training program can't play matches
+    pub fn is_in_training(&self) -> bool {
+        self.active_training.is_some()
+    }
+    
+    // Current age: starting age plus one year per competitive year since creation
+    pub fn age(&self, now: i64) -> u8 {
+        let years = std::cmp::max(0, now - self.created_at) / (SEASON_DURATION * SEASONS_PER_YEAR);
+        self.starting_age.saturating_add(std::cmp::min(years, u8::MAX as i64) as u8)
+    }
+    
+    // Form adjusted for the time since it was last stored: idle players drift
+    // back toward the baseline, which also means tired players recover with rest
+    pub fn current_form(&self, now: i64) -> u8 {
+        let elapsed = std::cmp::max(0, now - self.form_updated_at);
+        let drift = std::cmp::min(100, elapsed * FORM_DRIFT_PER_DAY / 86400) as u8;
+        
+        if self.form > FORM_BASELINE {
+            std::cmp::max(FORM_BASELINE, self.form.saturating_sub(drift))
+        } else {
+            std::cmp::min(FORM_BASELINE, self.form.saturating_add(drift))
+        }
+    }
+    
+    // Persist the time-adjusted form before applying any change to it
+    pub fn settle_form(&mut self, now: i64) {
+        self.form = self.current_form(now);
+        self.form_updated_at = now;
+    }
+    
+    // Potential as far as anyone can tell: the revealed value, or the middle of the public
+    // range while hidden (the program never knows the sealed value before it is opened)
+    pub fn potential_estimate(&self) -> u8 {
+        if self.potential_revealed {
+            self.potential
+        } else {
+            ((self.potential_min as u16 + self.potential_max as u16) / 2) as u8
+        }
+    }
+    
+    // Whether growth is capped: while hidden the whole range has to fit under 100
+    pub fn potential_maxed(&self) -> bool {
+        if self.potential_revealed {
+            self.potential >= 100
+        } else {
+            self.potential_max >= 100
+        }
+    }
+    
+    // Raise potential by one (the hidden range moves with it)
+    pub fn grow_potential(&mut self) {
+        if self.potential_maxed() {
+            return;
+        }
+        
+        if self.potential_revealed {
+            self.potential += 1;
+        } else {
+            self.potential_min += 1;
+            self.potential_max += 1;
+            self.potential_growth += 1;
+        }
+    }
+    
+    // Renter holding the use rights right now (if any)
+    pub fn active_user(&self, now: i64) -> Option<Pubkey> {
+        self.user.filter(|_| now < self.user_expires)
+    }
+    
+    // Whoever may roster and play the athlete: the renter while rented, else the owner
+    pub fn controller(&self, now: i64) -> Pubkey {
+        self.active_user(now).unwrap_or(self.owner)
+    }
+    
+    pub fn is_injured(&self, now: i64) -> bool {
+        now < self.injured_until
+    }
+    
+    // Flat stat adjustment from morale (-5 to +5)
+    pub fn morale_modifier(&self) -> i8 {
+        (self.morale as i16 - MORALE_BASELINE as i16) as i8 / 10
+    }
+    
+    // Read one of the five core stats
+    pub fn stat(&self, stat: &StatType) -> u8 {
+        match stat {
+            StatType::Mechanical => self.mechanical,
+            StatType::GameKnowledge => self.game_knowledge,
+            StatType::TeamCommunication => self.team_communication,
+            StatType::Adaptability => self.adaptability,
+            StatType::Consistency => self.consistency,
+        }
+    }
+    
+    // Core stats as [mechanical, game_knowledge, team_communication, adaptability, consistency]
+    pub fn core_stats(&self) -> [u8; 5] {
+        [
+            self.mechanical,
+            self.game_knowledge,
+            self.team_communication,
+            self.adaptability,
+            self.consistency,
+        ]
+    }
+    
+    pub fn has_ability(&self, ability_id: u16) -> bool {
+        self.special_abilities.iter().any(|a| a.ability_id == ability_id)
+    }
+    
+    pub fn has_ability_slot(&self) -> bool {
+        self.special_abilities.len() < MAX_SPECIAL_ABILITIES
+    }
 }
 
-// Special ability structure
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct SpecialAbility {
+// Player account layout before the current one (free-form position, named abilities)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyPlayerAccount {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub position: String,
+    pub created_at: i64,
+    pub last_updated: i64,
+    pub team: Option<Pubkey>,
+    pub uri: String,
+    pub mechanical: u8,
+    pub game_knowledge: u8,
+    pub team_communication: u8,
+    pub adaptability: u8,
+    pub consistency: u8,
+    pub special_abilities: Vec<LegacySpecialAbility>,
+    pub game_specific_data: Vec<u8>,
+    pub experience: u32,
+    pub matches_played: u32,
+    pub wins: u32,
+    pub mvp_count: u32,
+    pub form: u8,
+    pub potential: u8,
+    pub rarity: Rarity,
+    pub creator: Option<Pubkey>,
+    pub is_exclusive: bool,
+    pub performance_history: Vec<MatchPerformance>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacySpecialAbility {
     pub name: String,
     pub value: u8,
 }
 
+// Special ability structure (references an AbilityDefinition by id)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SpecialAbility {
+    pub ability_id: u16,
+    pub level: u8,
+    pub invested_experience: u32, // Spent on upgrades, partially refunded on respec
+    pub invested_lamports: u64,
+}
+
+impl SpecialAbility {
+    pub const LEN: usize =
+        2 + // ability_id
+        1 + // level
+        4 + // invested_experience
+        8; // invested_lamports
+    
+    pub fn new(ability_id: u16, level: u8) -> Self {
+        SpecialAbility {
+            ability_id,
+            level,
+            invested_experience: 0,
+            invested_lamports: 0,
+        }
+    }
+    
+    // A newly earned ability at the starting level
+    pub fn unlocked(ability_id: u16) -> Self {
+        SpecialAbility::new(ability_id, STARTING_ABILITY_LEVEL)
+    }
+}
+
+// Mastery of a single hero/champion
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HeroProficiency {
+    pub hero_id: u16,
+    pub mastery: u8, // 0-100
+    pub games_played: u16,
+}
+
+// Typed game-specific data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct GameSpecificData {
+    pub heroes: Vec<HeroProficiency>,
+}
+
+impl GameSpecificData {
+    pub fn hero_mastery(&self, hero_id: u16) -> u8 {
+        self.heroes.iter()
+            .find(|h| h.hero_id == hero_id)
+            .map_or(0, |h| h.mastery)
+    }
+    
+    // The hero the player would pick in a draft
+    pub fn comfort_pick(&self) -> Option<&HeroProficiency> {
+        self.heroes.iter().max_by_key(|h| (h.mastery, h.games_played))
+    }
+    
+    // Find a hero's entry, making room by dropping the least played hero when the pool is full
+    pub fn hero_entry(&mut self, hero_id: u16) -> &mut HeroProficiency {
+        if let Some(index) = self.heroes.iter().position(|h| h.hero_id == hero_id) {
+            return &mut self.heroes[index];
+        }
+        
+        if self.heroes.len() >= MAX_HERO_POOL {
+            let least_played = self.heroes.iter()
+                .enumerate()
+                .min_by_key(|(_, h)| (h.games_played, h.mastery))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            self.heroes.remove(least_played);
+        }
+        
+        self.heroes.push(HeroProficiency {
+            hero_id,
+            mastery: 0,
+            games_played: 0,
+        });
+        self.heroes.last_mut().unwrap()
+    }
+    
+    // Mastery grows with every game on a hero, faster with wins and MVPs
+    pub fn record_hero_game(&mut self, hero_id: u16, win: bool, mvp: bool) {
+        let hero = self.hero_entry(hero_id);
+        let gain = 1 + win as u8 + mvp as u8;
+        
+        hero.games_played = hero.games_played.saturating_add(1);
+        hero.mastery = std::cmp::min(100, hero.mastery.saturating_add(gain));
+    }
+}
+
+// Hero played in a match: the first two bytes of match_stats (little-endian)
+pub fn hero_from_match_stats(match_stats: &[u8]) -> Option<u16> {
+    match match_stats {
+        [low, high, ..] => Some(u16::from_le_bytes([*low, *high])),
+        _ => None,
+    }
+}
+
 // Match performance record
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MatchPerformance {
@@ -106,8 +537,8 @@ pub struct PlayerStats {
     pub form: u8,
 }
 
-// Rarity enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+// Rarity enum (ordered from lowest to highest tier)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, PartialOrd)]
 pub enum Rarity {
     Common,
     Uncommon,
@@ -116,6 +547,33 @@ pub enum Rarity {
     Legendary,
 }
 
+impl Rarity {
+    // The one potential-to-rarity mapping used by every code path
+    pub fn from_potential(potential: u8) -> Self {
+        match potential {
+            90..=u8::MAX => Rarity::Legendary,
+            80..=89 => Rarity::Epic,
+            70..=79 => Rarity::Rare,
+            60..=69 => Rarity::Uncommon,
+            _ => Rarity::Common,
+        }
+    }
+    
+    // This tier, raised to `floor` if it's lower
+    pub fn at_least(self, floor: Rarity) -> Self {
+        if self < floor { floor } else { self }
+    }
+}
+
+// Emitted whenever a player's rarity tier goes up
+#[event]
+pub struct RarityUpgraded {
+    pub mint: Pubkey,
+    pub previous_rarity: Rarity,
+    pub new_rarity: Rarity,
+    pub potential: u8, // Zero while the potential is hidden
+}
+
 // Training type enum
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum TrainingType {
@@ -126,6 +584,102 @@ pub enum TrainingType {
     Consistency,
 }
 
+// Roles for the supported MOBA format (Flex can cover any role)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum Position {
+    Top,
+    Jungle,
+    Mid,
+    Adc,
+    Support,
+    Flex,
+}
+
+// Stat penalties for playing outside a player's natural role
+pub const OFF_ROLE_PENALTY: u8 = 10;
+pub const FLEX_ROLE_PENALTY: u8 = 3;
+
+impl Position {
+    // Stat penalty for a player with this natural position playing `assigned`
+    pub fn role_penalty(&self, assigned: &Position) -> u8 {
+        if self == assigned || *assigned == Position::Flex {
+            0
+        } else if *self == Position::Flex {
+            FLEX_ROLE_PENALTY
+        } else {
+            OFF_ROLE_PENALTY
+        }
+    }
+}
+
+// Core stat selector
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum StatType {
+    Mechanical,
+    GameKnowledge,
+    TeamCommunication,
+    Adaptability,
+    Consistency,
+}
+
+impl StatType {
+    // Position in [mechanical, game_knowledge, team_communication, adaptability, consistency]
+    pub fn index(&self) -> usize {
+        match self {
+            StatType::Mechanical => 0,
+            StatType::GameKnowledge => 1,
+            StatType::TeamCommunication => 2,
+            StatType::Adaptability => 3,
+            StatType::Consistency => 4,
+        }
+    }
+}
+
+// Multi-day training program enum
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum TrainingProgram {
+    Bootcamp,
+    MechanicsGrind,
+    VodReview,
+}
+
+impl TrainingProgram {
+    // How long the player is locked into the program
+    pub fn duration(&self) -> i64 {
+        match self {
+            TrainingProgram::Bootcamp => 7 * 86400,
+            TrainingProgram::MechanicsGrind => 3 * 86400,
+            TrainingProgram::VodReview => 2 * 86400,
+        }
+    }
+    
+    // Base gains for [mechanical, game_knowledge, team_communication, adaptability, consistency]
+    pub fn base_gains(&self) -> [u8; 5] {
+        match self {
+            TrainingProgram::Bootcamp => [2, 2, 3, 2, 2],
+            TrainingProgram::MechanicsGrind => [4, 0, 0, 1, 3],
+            TrainingProgram::VodReview => [0, 4, 1, 3, 0],
+        }
+    }
+    
+    // Form change once the program is completed
+    pub fn form_change(&self) -> i8 {
+        match self {
+            TrainingProgram::Bootcamp => -5,
+            TrainingProgram::MechanicsGrind => -3,
+            TrainingProgram::VodReview => 5,
+        }
+    }
+}
+
+// Active training program record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ActiveTraining {
+    pub program: TrainingProgram,
+    pub started_at: i64,
+    pub unlocks_at: i64,
+}
+
 // Context for initializing a new player NFT
 #[derive(Accounts)]
 pub struct InitializePlayer<'info> {
@@ -143,6 +697,15 @@ pub struct InitializePlayer<'info> {
     )]
     pub player_account: Account<'info, PlayerAccount>,
     
+    // Passing these creates the Metaplex metadata (the owner must be the mint authority)
+    /// CHECK: Metaplex metadata PDA for the mint, verified in create_player_metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
@@ -158,9 +721,18 @@ pub struct UpdatePlayerPerformance<'info> {
         mut,
         seeds = [b"player", player_account.mint.as_ref()],
         bump,
-        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
     )]
     pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Metaplex metadata for the player mint, verified in sync_player_metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
 }
 
 // Context for training a player
@@ -173,11 +745,138 @@ pub struct TrainPlayer<'info> {
         mut,
         seeds = [b"player", player_account.mint.as_ref()],
         bump,
-        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for starting a training program
+#[derive(Accounts)]
+pub struct StartTrainingProgram<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed,
+        // A program would lock the player out of the renter's lineups for days
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for completing a training program
+#[derive(Accounts)]
+pub struct CompleteTrainingProgram<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for a reporter updating hero mastery
+#[derive(Accounts)]
+pub struct UpdateHeroMastery<'info> {
+    pub reporter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"reporter", reporter.key().as_ref()],
+        bump,
+        constraint = reporter_account.active @ ErrorCode::UnauthorizedAccess
+    )]
+    pub reporter_account: Account<'info, ReporterAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for retiring a player into the Hall of Fame
+#[derive(Accounts)]
+pub struct RetirePlayer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
     )]
     pub player_account: Account<'info, PlayerAccount>,
 }
 
+// Context for closing a player, burning the NFT and reclaiming rent
+#[derive(Accounts)]
+pub struct ClosePlayer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        mut,
+        address = player_account.mint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        constraint = owner_token_account.amount > 0 @ ErrorCode::UnauthorizedAccess
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    // Required for exclusive athletes so the creator's live athlete count stays accurate
+    #[account(mut)]
+    pub creator_account: Option<Account<'info, CreatorAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Context for moving a legacy player account to the current layout
+#[derive(Accounts)]
+pub struct MigratePlayerAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Legacy player account, validated and rewritten in migrate_player_account
+    #[account(mut, owner = crate::ID)]
+    pub player_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 // Context for adding special ability
 #[derive(Accounts)]
 pub struct AddSpecialAbility<'info> {
@@ -188,69 +887,85 @@ pub struct AddSpecialAbility<'info> {
         mut,
         seeds = [b"player", player_account.mint.as_ref()],
         bump,
-        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired
     )]
     pub player_account: Account<'info, PlayerAccount>,
     
-    // Optional admin account for authorized abilities
-    // pub admin: Option<Account<'info, AdminAccount>>,
+    // Catalog entry for the ability being granted
+    #[account(
+        seeds = [b"ability", ability_definition.ability_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ability_definition: Account<'info, AbilityDefinition>,
+    
+    /// CHECK: Metaplex metadata for the player mint, verified in sync_player_metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
 }
 
 // Initialize a new player NFT with starting attributes
 pub fn initialize_player(
     ctx: Context<InitializePlayer>,
     name: String,
-    position: String,
-    game_specific_data: Vec<u8>,
+    position: Position,
+    hero_pool: Vec<u16>,
     uri: String,
 ) -> Result<()> {
-    let player_account = &mut ctx.accounts.player_account;
+    require!(hero_pool.len() <= MAX_HERO_POOL, ErrorCode::InvalidHeroPool);
+    
     let clock = Clock::get()?;
     
-    player_account.owner = ctx.accounts.owner.key();
-    player_account.mint = ctx.accounts.mint.key();
-    player_account.name = name;
-    player_account.position = position;
-    player_account.created_at = clock.unix_timestamp;
-    player_account.last_updated = clock.unix_timestamp;
-    player_account.game_specific_data = game_specific_data;
-    player_account.uri = uri;
-    player_account.team = None;
-    player_account.creator = None;
-    player_account.is_exclusive = false;
+    // Starting age 16-22
+    let starting_age = 16 + (get_random_value(&clock, 7) % 7) as u8;
+    let mut player = PlayerAccount::new(
+        ctx.accounts.owner.key(),
+        ctx.accounts.mint.key(),
+        name,
+        position,
+        uri,
+        starting_age,
+        clock.unix_timestamp,
+    );
+    
+    for hero_id in hero_pool {
+        require!(
+            player.game_specific_data.hero_mastery(hero_id) == 0,
+            ErrorCode::InvalidHeroPool
+        );
+        player.game_specific_data.hero_entry(hero_id).mastery = STARTING_HERO_MASTERY;
+    }
     
     // Initialize base stats with randomized starting values
-    player_account.mechanical = 50 + (get_random_value(&clock, 0) % 31) as u8; // 50-80 range
-    player_account.game_knowledge = 50 + (get_random_value(&clock, 1) % 31) as u8;
-    player_account.team_communication = 50 + (get_random_value(&clock, 2) % 31) as u8;
-    player_account.adaptability = 50 + (get_random_value(&clock, 3) % 31) as u8;
-    player_account.consistency = 50 + (get_random_value(&clock, 4) % 31) as u8;
-    
-    // Initialize special abilities (if any)
-    player_account.special_abilities = Vec::new();
-    
-    // Initialize performance metrics
-    player_account.experience = 0;
-    player_account.matches_played = 0;
-    player_account.wins = 0;
-    player_account.mvp_count = 0;
-    player_account.form = 70;
-    player_account.potential = 50 + (get_random_value(&clock, 5) % 51) as u8; // 50-100 range
-    
-    // Initialize history
-    player_account.performance_history = Vec::new();
-    
-    // Set rarity based on potential
-    if player_account.potential >= 90 {
-        player_account.rarity = Rarity::Legendary;
-    } else if player_account.potential >= 80 {
-        player_account.rarity = Rarity::Epic;
-    } else if player_account.potential >= 70 {
-        player_account.rarity = Rarity::Rare;
-    } else if player_account.potential >= 60 {
-        player_account.rarity = Rarity::Uncommon;
-    } else {
-        player_account.rarity = Rarity::Common;
+    player.mechanical = 50 + (get_random_value(&clock, 0) % 31) as u8; // 50-80 range
+    player.game_knowledge = 50 + (get_random_value(&clock, 1) % 31) as u8;
+    player.team_communication = 50 + (get_random_value(&clock, 2) % 31) as u8;
+    player.adaptability = 50 + (get_random_value(&clock, 3) % 31) as u8;
+    player.consistency = 50 + (get_random_value(&clock, 4) % 31) as u8;
+    
+    // Set rarity based on potential; only the range around it is kept until a reporter seals it
+    let potential = 50 + (get_random_value(&clock, 5) % 51) as u8; // 50-100 range
+    player.set_minted_potential(Rarity::from_potential(potential), potential_window(potential));
+    
+    ctx.accounts.player_account.set_inner(player);
+    
+    if let (Some(metadata), Some(program)) = (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program) {
+        create_player_metadata(
+            &mut ctx.accounts.player_account,
+            PlayerMetadataAccounts {
+                metadata: metadata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                mint_authority: ctx.accounts.owner.to_account_info(),
+                payer: ctx.accounts.owner.to_account_info(),
+                token_metadata_program: program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+        )?;
     }
     
     Ok(())
@@ -274,6 +989,29 @@ pub fn update_player_performance(
     let player_account = &mut ctx.accounts.player_account;
     let clock = Clock::get()?;
     
+    // Players locked into a training program can't play matches
+    require!(!player_account.is_in_training(), ErrorCode::PlayerInTraining);
+    require!(!player_account.is_injured(clock.unix_timestamp), ErrorCode::PlayerInjured);
+    require!(
+        player_account.age(clock.unix_timestamp) <= MAX_PLAYING_AGE,
+        ErrorCode::PlayerTooOld
+    );
+    
+    apply_age_decay(player_account, clock.unix_timestamp);
+    let age = player_account.age(clock.unix_timestamp);
+    player_account.settle_form(clock.unix_timestamp);
+    
+    // Playing again without enough rest costs form and raises the injury risk
+    let fatigued = player_account.last_match_at > 0 &&
+        clock.unix_timestamp - player_account.last_match_at < FORM_REST_PERIOD;
+    if fatigued {
+        safe_update_stat(&mut player_account.form, BACK_TO_BACK_FORM_PENALTY);
+    }
+    player_account.last_match_at = clock.unix_timestamp;
+    
+    let injury_chance = if fatigued { FATIGUED_MATCH_INJURY_CHANCE } else { MATCH_INJURY_CHANCE };
+    roll_for_injury(player_account, &clock, injury_chance, 20);
+    
     // Update last updated timestamp
     player_account.last_updated = clock.unix_timestamp;
     
@@ -285,16 +1023,17 @@ pub fn update_player_performance(
     if mvp {
         player_account.mvp_count += 1;
     }
+    safe_update_stat(&mut player_account.morale, if win { MORALE_WIN } else { MORALE_LOSS });
     
     // Update experience
     player_account.experience += exp_gained;
     
-    // Apply stat changes (with limits)
-    safe_update_stat(&mut player_account.mechanical, mechanical_change);
-    safe_update_stat(&mut player_account.game_knowledge, game_knowledge_change);
-    safe_update_stat(&mut player_account.team_communication, team_communication_change);
-    safe_update_stat(&mut player_account.adaptability, adaptability_change);
-    safe_update_stat(&mut player_account.consistency, consistency_change);
+    // Apply stat changes (with limits, gains scaled by age)
+    safe_update_stat(&mut player_account.mechanical, age_adjusted_gain(mechanical_change, age));
+    safe_update_stat(&mut player_account.game_knowledge, age_adjusted_gain(game_knowledge_change, age));
+    safe_update_stat(&mut player_account.team_communication, age_adjusted_gain(team_communication_change, age));
+    safe_update_stat(&mut player_account.adaptability, age_adjusted_gain(adaptability_change, age));
+    safe_update_stat(&mut player_account.consistency, age_adjusted_gain(consistency_change, age));
     
     // Update form (can go up and down more freely, but still limited to 0-100)
     player_account.form = std::cmp::min(
@@ -305,11 +1044,25 @@ pub fn update_player_performance(
         ) as u8
     );
     
+    // Track mastery on the hero played, if the match stats name one
+    if let Some(hero_id) = hero_from_match_stats(&match_stats) {
+        player_account.game_specific_data.record_hero_game(hero_id, win, mvp);
+    }
+    
     // Add match to performance history
     add_match_to_history(player_account, match_id, win, mvp, match_stats, exp_gained, clock.unix_timestamp);
     
     // Check for leveling up/unlocking special abilities
-    check_for_level_ups(player_account);
+    let leveled_up = check_for_level_ups(player_account);
+    if leveled_up {
+        player_account.metadata_revision += 1;
+        sync_player_metadata_if_minted(
+            &ctx.accounts.player_account,
+            ctx.bumps.player_account,
+            &ctx.accounts.metadata,
+            &ctx.accounts.token_metadata_program,
+        )?;
+    }
     
     Ok(())
 }
@@ -324,6 +1077,13 @@ pub fn train_player(
     let player_account = &mut ctx.accounts.player_account;
     let clock = Clock::get()?;
     
+    // Individual drills aren't possible during a training program or while injured
+    require!(!player_account.is_in_training(), ErrorCode::PlayerInTraining);
+    require!(!player_account.is_injured(clock.unix_timestamp), ErrorCode::PlayerInjured);
+    
+    apply_age_decay(player_account, clock.unix_timestamp);
+    player_account.settle_form(clock.unix_timestamp);
+    
     // Calculate training effectiveness (based on intensity, player form, and some randomness)
     // Use checked math to prevent overflow
     let effectiveness = u8::try_from(
@@ -335,6 +1095,7 @@ pub fn train_player(
     // Calculate stat improvement (1-5 points typically)
     // Use saturating operations to prevent overflow
     let improvement = std::cmp::max(1, effectiveness.saturating_div(20).saturating_add(random_factor.max(0) as u8)) as i8;
+    let improvement = age_adjusted_gain(improvement, player_account.age(clock.unix_timestamp));
     
     // Apply improvement to the specific stat
     match training_type {
@@ -345,9 +1106,10 @@ pub fn train_player(
         TrainingType::Consistency => safe_update_stat(&mut player_account.consistency, improvement),
     }
     
-    // Training affects form (high intensity can reduce form)
+    // Training affects form (high intensity can reduce form and risk injury)
     if intensity > 70 {
         player_account.form = std::cmp::max(1, player_account.form.saturating_sub((intensity - 70) / 10));
+        roll_for_injury(player_account, &clock, ((intensity - 70) / 5) as u64, 21);
     }
     
     // Update last updated timestamp
@@ -356,28 +1118,270 @@ pub fn train_player(
     Ok(())
 }
 
-// Add special ability to player
-pub fn add_special_ability(
-    ctx: Context<AddSpecialAbility>,
-    ability_name: String,
-    ability_value: u8,
+// Lock a player into a multi-day training program
+pub fn start_training_program(
+    ctx: Context<StartTrainingProgram>,
+    program: TrainingProgram,
 ) -> Result<()> {
     let player_account = &mut ctx.accounts.player_account;
+    let clock = Clock::get()?;
+    
+    require!(!player_account.is_in_training(), ErrorCode::PlayerInTraining);
+    
+    // Injured players can only do VOD review
+    require!(
+        program == TrainingProgram::VodReview || !player_account.is_injured(clock.unix_timestamp),
+        ErrorCode::PlayerInjured
+    );
+    
+    let unlocks_at = clock.unix_timestamp + program.duration();
+    player_account.active_training = Some(ActiveTraining {
+        program,
+        started_at: clock.unix_timestamp,
+        unlocks_at,
+    });
+    
+    // Update last updated timestamp
+    player_account.last_updated = clock.unix_timestamp;
+    
+    msg!("Training program started, unlocks at {}", unlocks_at);
+    
+    Ok(())
+}
+
+// Finish a training program and apply its gains
+pub fn complete_training_program(ctx: Context<CompleteTrainingProgram>) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    let clock = Clock::get()?;
+    
+    let training = player_account.active_training.clone().ok_or(ErrorCode::NoActiveTraining)?;
+    
+    require!(
+        clock.unix_timestamp >= training.unlocks_at,
+        ErrorCode::TrainingNotComplete
+    );
+    
+    apply_age_decay(player_account, clock.unix_timestamp);
+    player_account.settle_form(clock.unix_timestamp);
+    apply_training_program_gains(player_account, &training.program, &clock);
+    
+    // A bootcamp is physically demanding
+    if training.program == TrainingProgram::Bootcamp {
+        roll_for_injury(player_account, &clock, BOOTCAMP_INJURY_CHANCE, 22);
+    }
+    
+    // Release the player from the program
+    player_account.active_training = None;
+    player_account.last_updated = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+// Set a player's mastery on a hero (authorized reporters only)
+pub fn update_hero_mastery(
+    ctx: Context<UpdateHeroMastery>,
+    hero_id: u16,
+    mastery: u8,
+    games_played: u16,
+) -> Result<()> {
+    require!(mastery <= 100, ErrorCode::InvalidHeroMastery);
+    
+    let player_account = &mut ctx.accounts.player_account;
+    
+    let hero = player_account.game_specific_data.hero_entry(hero_id);
+    hero.mastery = mastery;
+    hero.games_played = games_played;
+    
+    player_account.last_updated = Clock::get()?.unix_timestamp;
+    
+    Ok(())
+}
+
+// Retire a player: the account becomes a read-only Hall of Fame record
+pub fn retire_player(ctx: Context<RetirePlayer>) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    let clock = Clock::get()?;
+    
+    require!(!player_account.is_in_training(), ErrorCode::PlayerInTraining);
+    
+    // Settle any outstanding decay so the Hall of Fame stats are final
+    apply_age_decay(player_account, clock.unix_timestamp);
+    
+    player_account.retired = true;
+    player_account.retired_at = Some(clock.unix_timestamp);
+    player_account.last_updated = clock.unix_timestamp;
+    
+    msg!(
+        "{} retired at age {} after {} matches ({} wins, {} MVPs)",
+        player_account.name,
+        player_account.age(clock.unix_timestamp),
+        player_account.matches_played,
+        player_account.wins,
+        player_account.mvp_count
+    );
+    
+    Ok(())
+}
+
+// Close a player account: burns the NFT and returns rent to the owner
+pub fn close_player(ctx: Context<ClosePlayer>) -> Result<()> {
+    let player_account = &ctx.accounts.player_account;
+    
+    // Exclusive athletes count against their creator until burned
+    if let Some(creator) = player_account.creator {
+        let creator_account = ctx.accounts.creator_account
+            .as_mut()
+            .ok_or(ErrorCode::InvalidCreatorAccount)?;
+        require_keys_eq!(creator_account.key(), creator, ErrorCode::InvalidCreatorAccount);
+        creator_account.live_athletes = creator_account.live_athletes.saturating_sub(1);
+    }
+    
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        ctx.accounts.owner_token_account.amount,
+    )?;
+    
+    msg!("Player {} burned", player_account.name);
+    
+    Ok(())
+}
+
+// Grant a catalog ability to a player once its unlock conditions are met
+pub fn add_special_ability(ctx: Context<AddSpecialAbility>) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    let ability_definition = &ctx.accounts.ability_definition;
     
     // Check if already has this ability
-    if player_account.special_abilities.iter().any(|a| a.name == ability_name) {
+    if player_account.has_ability(ability_definition.ability_id) {
         return Err(ErrorCode::AbilityAlreadyExists.into());
     }
+    require!(player_account.has_ability_slot(), ErrorCode::AbilitySlotsFull);
     
-    // Add the new ability
-    player_account.special_abilities.push(SpecialAbility {
-        name: ability_name,
-        value: ability_value,
-    });
+    require!(
+        player_account.rarity >= ability_definition.required_rarity,
+        ErrorCode::RarityTooLow
+    );
+    
+    require!(
+        ability_definition.is_unlocked_for(player_account),
+        ErrorCode::AbilityNotUnlocked
+    );
+    
+    player_account.special_abilities.push(SpecialAbility::unlocked(ability_definition.ability_id));
+    player_account.metadata_revision += 1;
+    
+    sync_player_metadata_if_minted(
+        &ctx.accounts.player_account,
+        ctx.bumps.player_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+    )
+}
+
+// Grow a legacy player account and rewrite it in the current layout
+// The old potential was public, so it comes across revealed; hero data and custom abilities don't carry over
+pub fn migrate_player_account(ctx: Context<MigratePlayerAccount>) -> Result<()> {
+    let player_info = ctx.accounts.player_account.to_account_info();
+    require!(player_info.data_len() == LEGACY_PLAYER_ACCOUNT_LEN, ErrorCode::PlayerAlreadyMigrated);
+    
+    let legacy = {
+        let data = player_info.try_borrow_data()?;
+        require!(
+            data[..8] == PlayerAccount::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyPlayerAccount::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(legacy.owner, ctx.accounts.owner.key(), ErrorCode::UnauthorizedAccess);
+    
+    // The owner covers the rent for the larger account
+    let rent = Rent::get()?.minimum_balance(PlayerAccount::LEN);
+    let shortfall = rent.saturating_sub(player_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: player_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    player_info.realloc(PlayerAccount::LEN, true)?;
+    
+    let clock = Clock::get()?;
+    let mut player = PlayerAccount::new(
+        legacy.owner,
+        legacy.mint,
+        legacy.name,
+        legacy_position(&legacy.position),
+        legacy.uri,
+        16 + (get_random_value(&clock, 7) % 7) as u8,
+        legacy.created_at,
+    );
+    player.last_updated = clock.unix_timestamp;
+    player.team = legacy.team;
+    player.mechanical = legacy.mechanical;
+    player.game_knowledge = legacy.game_knowledge;
+    player.team_communication = legacy.team_communication;
+    player.adaptability = legacy.adaptability;
+    player.consistency = legacy.consistency;
+    player.experience = legacy.experience;
+    player.matches_played = legacy.matches_played;
+    player.wins = legacy.wins;
+    player.mvp_count = legacy.mvp_count;
+    player.form = legacy.form;
+    player.form_updated_at = clock.unix_timestamp;
+    player.creator = legacy.creator;
+    player.is_exclusive = legacy.is_exclusive;
+    player.performance_history = legacy.performance_history;
+    
+    player.set_minted_potential(legacy.rarity, potential_window(legacy.potential));
+    player.potential = legacy.potential;
+    player.potential_revealed = true;
+    
+    // Built-in abilities were stored by name; the creator ability went to every exclusive athlete
+    for ability in legacy.special_abilities.iter() {
+        if let Some(ability_id) = legacy_ability_id(&ability.name) {
+            player.special_abilities.push(SpecialAbility::unlocked(ability_id));
+        }
+    }
+    if legacy.is_exclusive && player.has_ability_slot() {
+        player.special_abilities.push(SpecialAbility::unlocked(CREATOR_SIGNATURE_ID));
+    }
+    
+    let mut data = player_info.try_borrow_mut_data()?;
+    player.try_serialize(&mut &mut data[..])?;
     
     Ok(())
 }
 
+// Map a legacy ability name onto a built-in ability id
+fn legacy_ability_id(name: &str) -> Option<u16> {
+    match name {
+        "Clutch Factor" => Some(CLUTCH_FACTOR_ID),
+        "Perfect Execution" => Some(PERFECT_EXECUTION_ID),
+        "Shot Caller" => Some(SHOT_CALLER_ID),
+        _ => None,
+    }
+}
+
+// Deserialize a player account passed in through remaining accounts
+pub fn load_player_account(account_info: &AccountInfo) -> Result<PlayerAccount> {
+    require!(account_info.owner == &crate::ID, ErrorCode::UnauthorizedAccess);
+    let data = account_info.try_borrow_data()?;
+    PlayerAccount::try_deserialize(&mut &data[..])
+}
+
 // Add a match performance to player history (keeping only recent matches)
 fn add_match_to_history(
     player: &mut PlayerAccount,
@@ -406,34 +1410,140 @@ fn add_match_to_history(
     }
 }
 
+// Apply the stat gains of a completed training program
+// Gains scale with form and potential: a sharp, high-ceiling player gets up to double the base
+fn apply_training_program_gains(
+    player: &mut PlayerAccount,
+    program: &TrainingProgram,
+    clock: &Clock,
+) {
+    let multiplier = player.form as u16 + player.potential_estimate() as u16; // 0-200 (percent)
+    let base_gains = program.base_gains();
+    let age = player.age(clock.unix_timestamp);
+    
+    let mut gains = [0i8; 5];
+    for (i, base) in base_gains.iter().enumerate() {
+        if *base == 0 {
+            continue;
+        }
+        let random_bonus = (get_random_value(clock, 10 + i as u8) % 2) as u16; // 0-1 bonus
+        let gain = (*base as u16 * multiplier / 100 + random_bonus).clamp(1, 10);
+        gains[i] = age_adjusted_gain(gain as i8, age);
+    }
+    
+    safe_update_stat(&mut player.mechanical, gains[0]);
+    safe_update_stat(&mut player.game_knowledge, gains[1]);
+    safe_update_stat(&mut player.team_communication, gains[2]);
+    safe_update_stat(&mut player.adaptability, gains[3]);
+    safe_update_stat(&mut player.consistency, gains[4]);
+    safe_update_stat(&mut player.form, program.form_change());
+}
+
+// Randomly injure a player for 1 to MAX_INJURY_DAYS days
+fn roll_for_injury(player: &mut PlayerAccount, clock: &Clock, chance_percent: u64, seed: u8) {
+    if get_random_value(clock, seed) % 100 >= chance_percent {
+        return;
+    }
+    
+    let days = 1 + (get_random_value(clock, seed.wrapping_add(1)) >> 8) % MAX_INJURY_DAYS;
+    let injured_until = clock.unix_timestamp + days as i64 * 86400;
+    player.injured_until = std::cmp::max(player.injured_until, injured_until);
+    
+    msg!("{} picked up an injury for {} days", player.name, days);
+}
+
+// Scale a positive stat gain by age: faster growth before peak, slower after it
+pub fn age_adjusted_gain(change: i8, age: u8) -> i8 {
+    if change <= 0 {
+        return change;
+    }
+    
+    let percent: i16 = if age < PEAK_AGE_START {
+        150
+    } else if age <= PEAK_AGE_END {
+        100
+    } else {
+        50
+    };
+    
+    std::cmp::min(i8::MAX as i16, std::cmp::max(1, change as i16 * percent / 100)) as i8
+}
+
+// Apply post-peak decay for every year the player has aged since the last check
+fn apply_age_decay(player: &mut PlayerAccount, now: i64) {
+    let age = player.age(now);
+    
+    while player.last_aged_at < age {
+        player.last_aged_at += 1;
+        
+        if player.last_aged_at > PEAK_AGE_END {
+            // Reflexes fade first, game sense and communication hold up
+            safe_update_stat(&mut player.mechanical, -3);
+            safe_update_stat(&mut player.adaptability, -2);
+            safe_update_stat(&mut player.consistency, -1);
+        }
+    }
+}
+
+// Public range of POTENTIAL_RANGE_WIDTH centred on a rolled potential (kept inside 0-100)
+pub fn potential_window(potential: u8) -> (u8, u8) {
+    let min = std::cmp::min(potential.saturating_sub(POTENTIAL_RANGE_WIDTH / 2), 100 - POTENTIAL_RANGE_WIDTH);
+    (min, min + POTENTIAL_RANGE_WIDTH)
+}
+
+// Re-derive rarity after a potential change (never below the minted tier for exclusives)
+// Returns whether the tier changed
+pub fn refresh_rarity(player: &mut PlayerAccount) -> bool {
+    let floor = if player.is_exclusive { player.minted_rarity } else { Rarity::Common };
+    let rarity = Rarity::from_potential(player.potential_estimate()).at_least(floor);
+    
+    if rarity == player.rarity {
+        return false;
+    }
+    
+    if rarity > player.rarity {
+        emit!(RarityUpgraded {
+            mint: player.mint,
+            previous_rarity: player.rarity,
+            new_rarity: rarity,
+            potential: player.potential,
+        });
+    }
+    
+    player.rarity = rarity;
+    true
+}
+
 // Check for level ups or special ability unlocks based on experience and performance
-fn check_for_level_ups(player: &mut PlayerAccount) {
+// Returns whether the player progressed
+fn check_for_level_ups(player: &mut PlayerAccount) -> bool {
+    let mut progressed = false;
+    
     // Potential increases slightly based on performance
-    if player.matches_played % 10 == 0 && player.wins > player.matches_played / 2 {
-        player.potential = std::cmp::min(100, player.potential + 1);
+    if player.matches_played % 10 == 0 && player.wins > player.matches_played / 2 && !player.potential_maxed() {
+        player.grow_potential();
+        refresh_rarity(player);
+        progressed = true;
     }
     
     // Unlock special abilities based on performance thresholds
-    if player.mvp_count >= 5 && !player.special_abilities.iter().any(|a| a.name == "Clutch Factor") {
-        player.special_abilities.push(SpecialAbility {
-            name: String::from("Clutch Factor"),
-            value: 50 + (player.mvp_count as u8 / 2),
-        });
+    // (only while the player has a free ability slot)
+    if player.mvp_count >= 5 && !player.has_ability(CLUTCH_FACTOR_ID) && player.has_ability_slot() {
+        player.special_abilities.push(SpecialAbility::unlocked(CLUTCH_FACTOR_ID));
+        progressed = true;
     }
     
     // Additional ability unlocks based on specialized performance
-    if player.mechanical >= 90 && !player.special_abilities.iter().any(|a| a.name == "Perfect Execution") {
-        player.special_abilities.push(SpecialAbility {
-            name: String::from("Perfect Execution"),
-            value: player.mechanical - 30,
-        });
+    if player.mechanical >= 90 && !player.has_ability(PERFECT_EXECUTION_ID) && player.has_ability_slot() {
+        player.special_abilities.push(SpecialAbility::unlocked(PERFECT_EXECUTION_ID));
+        progressed = true;
     }
     
     if player.team_communication >= 85 && player.matches_played >= 20 && 
-       !player.special_abilities.iter().any(|a| a.name == "Shot Caller") {
-        player.special_abilities.push(SpecialAbility {
-            name: String::from("Shot Caller"),
-            value: 70 + ((player.team_communication - 85) / 3),
-        });
+       !player.has_ability(SHOT_CALLER_ID) && player.has_ability_slot() {
+        player.special_abilities.push(SpecialAbility::unlocked(SHOT_CALLER_ID));
+        progressed = true;
     }
+    
+    progressed
 }
\ No newline at end of file
@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+use crate::contract::PlayerContract;
+use crate::player::PlayerAccount;
+use crate::team::{refresh_roster_statistics, remove_from_roster, TeamAccount};
+use crate::utils::move_lamports;
+
+// Rental fees are quoted and released per period
+pub const RENTAL_PERIOD: i64 = 86400;
+
+// Longest single rental
+pub const MAX_RENTAL_PERIODS: u16 = 90;
+
+// Player Rental Structure (one per player; fees are escrowed in this account)
+#[account]
+pub struct PlayerRental {
+    pub player: Pubkey,
+    pub owner: Pubkey,
+    pub fee_per_period: u64,
+    pub max_periods: u16,
+    pub renter: Option<Pubkey>,
+    pub periods: u16,
+    pub periods_claimed: u16,
+    pub started_at: i64,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl PlayerRental {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // player pubkey
+        32 + // owner pubkey
+        8 + // fee_per_period
+        2 + // max_periods
+        33 + // renter (Option<Pubkey>)
+        2 + // periods
+        2 + // periods_claimed
+        8 + // started_at
+        8; // created_at
+    
+    // Periods that have fully elapsed
+    pub fn periods_completed(&self, now: i64) -> u16 {
+        let elapsed = std::cmp::max(0, now - self.started_at);
+        std::cmp::min(elapsed / RENTAL_PERIOD, self.periods as i64) as u16
+    }
+    
+    // Periods that have at least begun (the current one is owed on an early return)
+    pub fn periods_started(&self, now: i64) -> u16 {
+        let elapsed = std::cmp::max(0, now - self.started_at);
+        std::cmp::min((elapsed + RENTAL_PERIOD - 1) / RENTAL_PERIOD, self.periods as i64) as u16
+    }
+    
+    pub fn expires_at(&self) -> i64 {
+        self.started_at + self.periods as i64 * RENTAL_PERIOD
+    }
+}
+
+// Context for offering a player's use rights for rent
+#[derive(Accounts)]
+pub struct ListPlayerForRent<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = player_account.user.is_none() @ ErrorCode::PlayerRented,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PlayerRental::LEN,
+        seeds = [b"rental", player_account.key().as_ref()],
+        bump
+    )]
+    pub player_rental: Account<'info, PlayerRental>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for withdrawing a rental offer nobody has taken
+#[derive(Accounts)]
+pub struct CancelRentalListing<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"rental", player_rental.player.as_ref()],
+        bump,
+        constraint = player_rental.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_rental.renter.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_rental: Account<'info, PlayerRental>,
+}
+
+// Context for renting a player
+#[derive(Accounts)]
+pub struct RentPlayer<'info> {
+    #[account(mut)]
+    pub renter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"rental", player_account.key().as_ref()],
+        bump,
+        constraint = player_rental.renter.is_none() @ ErrorCode::PlayerRented
+    )]
+    pub player_rental: Account<'info, PlayerRental>,
+    
+    #[account(
+        mut,
+        address = player_rental.player,
+        constraint = player_account.owner == player_rental.owner @ ErrorCode::UnauthorizedAccess,
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the owner collecting fees for elapsed periods
+#[derive(Accounts)]
+pub struct ClaimRentalFees<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"rental", player_rental.player.as_ref()],
+        bump,
+        constraint = player_rental.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = player_rental.renter.is_some() @ ErrorCode::RentalNotActive
+    )]
+    pub player_rental: Account<'info, PlayerRental>,
+}
+
+// Context for ending a rental (the renter at any time, anyone after expiry)
+#[derive(Accounts)]
+pub struct EndRental<'info> {
+    pub caller: Signer<'info>,
+    
+    // Closing returns the rent to the owner
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"rental", player_account.key().as_ref()],
+        bump,
+        constraint = player_rental.renter.is_some() @ ErrorCode::RentalNotActive
+    )]
+    pub player_rental: Account<'info, PlayerRental>,
+    
+    #[account(
+        mut,
+        address = player_rental.player
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    /// CHECK: Player owner, receives the earned fees
+    #[account(
+        mut,
+        address = player_rental.owner
+    )]
+    pub owner: UncheckedAccount<'info>,
+    
+    /// CHECK: Renter, refunded for periods that never started
+    #[account(
+        mut,
+        constraint = Some(renter.key()) == player_rental.renter @ ErrorCode::UnauthorizedAccess
+    )]
+    pub renter: UncheckedAccount<'info>,
+    
+    // Required if the renter still has the player rostered
+    #[account(mut)]
+    pub team_account: Option<Account<'info, TeamAccount>>,
+    
+    #[account(mut)]
+    pub player_contract: Option<Account<'info, PlayerContract>>,
+    
+    /// CHECK: Team owner, gets the contract rent back
+    #[account(mut)]
+    pub team_owner: Option<UncheckedAccount<'info>>,
+}
+
+// Offer a teamless player's use rights at a fee per period
+pub fn list_player_for_rent(
+    ctx: Context<ListPlayerForRent>,
+    fee_per_period: u64,
+    max_periods: u16,
+) -> Result<()> {
+    require!(
+        max_periods > 0 && max_periods <= MAX_RENTAL_PERIODS,
+        ErrorCode::InvalidRentalTerms
+    );
+    
+    let player_rental = &mut ctx.accounts.player_rental;
+    let clock = Clock::get()?;
+    
+    player_rental.player = ctx.accounts.player_account.key();
+    player_rental.owner = ctx.accounts.owner.key();
+    player_rental.fee_per_period = fee_per_period;
+    player_rental.max_periods = max_periods;
+    player_rental.renter = None;
+    player_rental.periods = 0;
+    player_rental.periods_claimed = 0;
+    player_rental.started_at = 0;
+    player_rental.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+pub fn cancel_rental_listing(_ctx: Context<CancelRentalListing>) -> Result<()> {
+    Ok(())
+}
+
+// Rent a player for a number of periods, escrowing the full fee up front
+pub fn rent_player(ctx: Context<RentPlayer>, periods: u16) -> Result<()> {
+    require!(
+        periods > 0 && periods <= ctx.accounts.player_rental.max_periods,
+        ErrorCode::InvalidRentalTerms
+    );
+    
+    let total_fee = ctx.accounts.player_rental.fee_per_period
+        .checked_mul(periods as u64)
+        .ok_or(ErrorCode::InvalidRentalTerms)?;
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.renter.to_account_info(),
+                to: ctx.accounts.player_rental.to_account_info(),
+            },
+        ),
+        total_fee,
+    )?;
+    
+    let clock = Clock::get()?;
+    let player_rental = &mut ctx.accounts.player_rental;
+    let player_account = &mut ctx.accounts.player_account;
+    
+    player_rental.renter = Some(ctx.accounts.renter.key());
+    player_rental.periods = periods;
+    player_rental.periods_claimed = 0;
+    player_rental.started_at = clock.unix_timestamp;
+    
+    player_account.user = Some(ctx.accounts.renter.key());
+    player_account.user_expires = player_rental.expires_at();
+    
+    msg!("{} rented until {}", player_account.name, player_account.user_expires);
+    
+    Ok(())
+}
+
+// Release the fees for periods that have fully elapsed to the owner
+pub fn claim_rental_fees(ctx: Context<ClaimRentalFees>) -> Result<()> {
+    let clock = Clock::get()?;
+    let player_rental = &mut ctx.accounts.player_rental;
+    
+    let periods = player_rental.periods_completed(clock.unix_timestamp) - player_rental.periods_claimed;
+    require!(periods > 0, ErrorCode::NoRentalFeesDue);
+    
+    let amount = player_rental.fee_per_period * periods as u64;
+    player_rental.periods_claimed += periods;
+    
+    move_lamports(&player_rental.to_account_info(), &ctx.accounts.owner.to_account_info(), amount)
+}
+
+// End a rental: settles fees, hands the use rights back and frees any roster slot the renter used
+// (when the player is rostered, pass the rest of that roster as remaining accounts)
+pub fn end_rental(ctx: Context<EndRental>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let player_rental = &ctx.accounts.player_rental;
+    
+    // The renter may return early; anyone can end an expired rental
+    require!(
+        now >= player_rental.expires_at() || Some(ctx.accounts.caller.key()) == player_rental.renter,
+        ErrorCode::RentalNotEnded
+    );
+    
+    // Started periods are owed to the owner, the rest is refunded
+    let started = player_rental.periods_started(now);
+    let owed = player_rental.fee_per_period * (started - player_rental.periods_claimed) as u64;
+    let refund = player_rental.fee_per_period * (player_rental.periods - started) as u64;
+    
+    let rental_info = player_rental.to_account_info();
+    move_lamports(&rental_info, &ctx.accounts.owner.to_account_info(), owed)?;
+    move_lamports(&rental_info, &ctx.accounts.renter.to_account_info(), refund)?;
+    
+    let player_account = &mut ctx.accounts.player_account;
+    
+    // Take the player off the renter's roster and tear up that contract
+    if let Some(team_key) = player_account.team {
+        let (Some(team_account), Some(player_contract), Some(team_owner)) = (
+            ctx.accounts.team_account.as_mut(),
+            ctx.accounts.player_contract.as_mut(),
+            ctx.accounts.team_owner.as_ref(),
+        ) else {
+            return Err(ErrorCode::PlayerNotOnTeam.into());
+        };
+        
+        require_keys_eq!(team_account.key(), team_key, ErrorCode::PlayerNotOnTeam);
+        require_keys_eq!(team_owner.key(), team_account.owner, ErrorCode::UnauthorizedAccess);
+        require!(
+            player_contract.team == team_key && player_contract.player == player_account.key(),
+            ErrorCode::InvalidContractTerms
+        );
+        
+        remove_from_roster(team_account, &player_account.mint)?;
+        team_account.last_updated = now;
+        player_account.team = None;
+        player_contract.close(team_owner.to_account_info())?;
+        refresh_roster_statistics(team_account, team_key, ctx.remaining_accounts, None)?;
+    }
+    
+    player_account.user = None;
+    player_account.user_expires = 0;
+    
+    msg!("Rental of {} ended", player_account.name);
+    
+    Ok(())
+}
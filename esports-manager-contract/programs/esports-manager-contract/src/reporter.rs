@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::config::ProgramConfig;
+
+// Reporter Account Structure (a match data reporter authorized by the config authority)
+#[account]
+pub struct ReporterAccount {
+    pub reporter: Pubkey,
+    pub authorized_by: Pubkey,
+    pub active: bool,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl ReporterAccount {
+    pub const LEN: usize = 
+        8 + // discriminator
+        32 + // reporter pubkey
+        32 + // authorized_by pubkey
+        1 + // active
+        8; // created_at
+}
+
+// Context for authorizing a reporter
+#[derive(Accounts)]
+#[instruction(reporter: Pubkey)]
+pub struct AuthorizeReporter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ReporterAccount::LEN,
+        seeds = [b"reporter", reporter.as_ref()],
+        bump
+    )]
+    pub reporter_account: Account<'info, ReporterAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Context for revoking a reporter
+#[derive(Accounts)]
+pub struct RevokeReporter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    // Closed so the same key can be authorized again later
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"reporter", reporter_account.reporter.as_ref()],
+        bump
+    )]
+    pub reporter_account: Account<'info, ReporterAccount>,
+}
+
+// Authorize a key to report match data
+pub fn authorize_reporter(ctx: Context<AuthorizeReporter>, reporter: Pubkey) -> Result<()> {
+    let reporter_account = &mut ctx.accounts.reporter_account;
+    let clock = Clock::get()?;
+    
+    reporter_account.reporter = reporter;
+    reporter_account.authorized_by = ctx.accounts.authority.key();
+    reporter_account.active = true;
+    reporter_account.created_at = clock.unix_timestamp;
+    
+    Ok(())
+}
+
+// Revoke a reporter's authorization, closing its account
+pub fn revoke_reporter(ctx: Context<RevokeReporter>) -> Result<()> {
+    msg!("Reporter {} revoked", ctx.accounts.reporter_account.reporter);
+    
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+use crate::metadata::sync_player_metadata_if_minted;
+use crate::player::{refresh_rarity, PlayerAccount};
+use crate::reporter::ReporterAccount;
+
+// Fee paid to the treasury for a scouting report
+pub const SCOUT_FEE: u64 = 5_000_000; // 0.005 SOL
+
+// Widest range a fulfilled scouting report may give
+pub const SCOUTED_RANGE_WIDTH: u8 = 4;
+
+// Matches a player must play before the commitment can be opened
+pub const REVEAL_MIN_MATCHES: u32 = 20;
+
+// Scouting Report Structure (one per scout and player)
+#[account]
+pub struct ScoutReport {
+    pub scout: Pubkey,
+    pub player: Pubkey,
+    pub potential_min: u8,
+    pub potential_max: u8,
+    pub fulfilled: bool,
+    pub requested_at: i64,
+    pub fulfilled_at: Option<i64>,
+}
+
+// Fixed size for account allocation
+impl ScoutReport {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // scout pubkey
+        32 + // player pubkey
+        1 + // potential_min
+        1 + // potential_max
+        1 + // fulfilled
+        8 + // requested_at
+        9; // fulfilled_at (Option<i64>)
+}
+
+// Commitment over a player's true potential: sha256(mint || potential || salt), where the salt
+// is a secret the sealing reporter keeps until the reveal
+pub fn potential_commitment(mint: &Pubkey, potential: u8, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[mint.as_ref(), &[potential], salt]).to_bytes()
+}
+
+// Context for a reporter sealing a player's true potential
+#[derive(Accounts)]
+pub struct SealPotential<'info> {
+    pub reporter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"reporter", reporter.key().as_ref()],
+        bump,
+        constraint = reporter_account.active @ ErrorCode::UnauthorizedAccess
+    )]
+    pub reporter_account: Account<'info, ReporterAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.potential_commitment.is_none() @ ErrorCode::PotentialAlreadyCommitted,
+        constraint = !player_account.potential_revealed @ ErrorCode::PotentialAlreadyRevealed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for ordering a scouting report
+#[derive(Accounts)]
+pub struct ScoutPlayer<'info> {
+    #[account(mut)]
+    pub scout: Signer<'info>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = player_account.potential_commitment.is_some() @ ErrorCode::PotentialNotCommitted,
+        constraint = !player_account.potential_revealed @ ErrorCode::PotentialAlreadyRevealed
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    #[account(
+        init,
+        payer = scout,
+        space = ScoutReport::LEN,
+        seeds = [b"scout", scout.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub scout_report: Account<'info, ScoutReport>,
+    
+    /// CHECK: Protocol treasury PDA, only receives lamports
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for a reporter filling in a scouting report
+#[derive(Accounts)]
+pub struct FulfillScoutReport<'info> {
+    pub reporter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"reporter", reporter.key().as_ref()],
+        bump,
+        constraint = reporter_account.active @ ErrorCode::UnauthorizedAccess
+    )]
+    pub reporter_account: Account<'info, ReporterAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"scout", scout_report.scout.as_ref(), player_account.key().as_ref()],
+        bump,
+        constraint = !scout_report.fulfilled @ ErrorCode::ScoutReportFulfilled
+    )]
+    pub scout_report: Account<'info, ScoutReport>,
+    
+    #[account(
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+}
+
+// Context for opening a potential commitment
+#[derive(Accounts)]
+pub struct RevealPotential<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", player_account.mint.as_ref()],
+        bump,
+        constraint = !player_account.potential_revealed @ ErrorCode::PotentialAlreadyRevealed,
+        constraint = player_account.matches_played >= REVEAL_MIN_MATCHES @ ErrorCode::RevealTooEarly
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    
+    // Passing these keeps the NFT metadata in sync with the revealed tier (required once minted)
+    /// CHECK: Metaplex metadata for the player mint, verified in sync_player_metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = anchor_spl::metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+}
+
+// Seal the true potential of a player: the reporter picks it inside the public range off-chain
+// and only the commitment is stored, so nobody can work it out before the reveal
+pub fn seal_potential(ctx: Context<SealPotential>, commitment: [u8; 32]) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    
+    player_account.potential_commitment = Some(commitment);
+    
+    msg!("Potential of {} sealed", player_account.name);
+    
+    Ok(())
+}
+
+// Pay for a scouting report; a reporter narrows the range for this scout
+pub fn scout_player(ctx: Context<ScoutPlayer>) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.scout.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        ),
+        SCOUT_FEE,
+    )?;
+    
+    let player_account = &ctx.accounts.player_account;
+    let scout_report = &mut ctx.accounts.scout_report;
+    let clock = Clock::get()?;
+    
+    // Until fulfilled, the report only knows the public range
+    scout_report.scout = ctx.accounts.scout.key();
+    scout_report.player = player_account.key();
+    scout_report.potential_min = player_account.potential_min;
+    scout_report.potential_max = player_account.potential_max;
+    scout_report.fulfilled = false;
+    scout_report.requested_at = clock.unix_timestamp;
+    scout_report.fulfilled_at = None;
+    
+    Ok(())
+}
+
+// Write the narrowed range into a scouting report. Only the sealing reporter knows the committed
+// value, so the program can only check the range against the public one
+pub fn fulfill_scout_report(
+    ctx: Context<FulfillScoutReport>,
+    potential_min: u8,
+    potential_max: u8,
+) -> Result<()> {
+    let player_account = &ctx.accounts.player_account;
+    let scout_report = &mut ctx.accounts.scout_report;
+    
+    require!(
+        potential_min <= potential_max &&
+            potential_max - potential_min <= SCOUTED_RANGE_WIDTH &&
+            potential_min >= player_account.potential_min &&
+            potential_max <= player_account.potential_max,
+        ErrorCode::InvalidScoutRange
+    );
+    
+    scout_report.potential_min = potential_min;
+    scout_report.potential_max = potential_max;
+    scout_report.fulfilled = true;
+    scout_report.fulfilled_at = Some(Clock::get()?.unix_timestamp);
+    
+    Ok(())
+}
+
+// Open the commitment with the sealed value and the reporter's salt, making the true potential
+// (plus growth since) public
+pub fn reveal_potential(ctx: Context<RevealPotential>, potential: u8, salt: [u8; 32]) -> Result<()> {
+    let player_account = &mut ctx.accounts.player_account;
+    
+    let commitment = player_account.potential_commitment.ok_or(ErrorCode::PotentialNotCommitted)?;
+    require!(
+        potential_commitment(&player_account.mint, potential, &salt) == commitment,
+        ErrorCode::InvalidPotentialReveal
+    );
+    
+    // The sealed value has to lie in the range shown at mint (growth has shifted it since)
+    let growth = player_account.potential_growth;
+    require!(
+        potential >= player_account.potential_min - growth && potential <= player_account.potential_max - growth,
+        ErrorCode::InvalidPotentialReveal
+    );
+    
+    let revealed = std::cmp::min(100, potential + growth);
+    player_account.potential = revealed;
+    player_account.potential_min = revealed;
+    player_account.potential_max = revealed;
+    player_account.potential_revealed = true;
+    player_account.metadata_revision += 1;
+    refresh_rarity(player_account);
+    
+    msg!("{} has {} potential", player_account.name, revealed);
+    
+    sync_player_metadata_if_minted(
+        &ctx.accounts.player_account,
+        ctx.bumps.player_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+    )
+}
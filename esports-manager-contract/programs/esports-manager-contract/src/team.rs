@@ -1,7 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{system_program, Discriminator};
 use anchor_spl::token::Mint;
 use crate::errors::ErrorCode;
-use crate::player::PlayerAccount;
+use crate::player::{load_player_account, PlayerAccount, Position, MORALE_BENCHED, MORALE_ROSTERED};
+use crate::utils::safe_update_stat;
+use crate::contract::{sign_player_contract, PlayerContract};
+use crate::ability::{
+    calculate_ability_modifiers, AbilityDefinition, AbilityModifiers, MatchSituation, MAX_ABILITY_BONUS,
+};
+
+// Most player pairs a five-player roster can form
+pub const MAX_SYNERGY_PAIRS: usize = 10;
+
+// Shared matches after which a pair's history bonus is maxed out
+pub const SYNERGY_HISTORY_CAP: u16 = 25;
+
+// Recent matches kept on the team account (full history stored off-chain)
+pub const MAX_MATCH_HISTORY: usize = 10;
+pub const MAX_MATCH_ID_LEN: usize = 32;
+
+// Size of team accounts created before roster positions, stat averages and synergy existed
+pub const LEGACY_TEAM_ACCOUNT_LEN: usize = 634;
 
 // Team Account Structure
 #[account]
@@ -15,6 +35,7 @@ pub struct TeamAccount {
     pub roster: Vec<RosterPosition>,      // List of players and their positions
     pub statistics: TeamStatistics,
     pub match_history: Vec<TeamMatchResult>,
+    pub synergy_pairs: Vec<SynergyPair>,  // One entry per pair of rostered players
 }
 
 // Fixed size for account allocation
@@ -27,16 +48,17 @@ impl TeamAccount {
         36 + // logo_uri (max 32 chars)
         8 + // created_at
         8 + // last_updated
-        200 + // roster (variable size, estimate for 5 players)
+        209 + // roster (5 players)
         64 + // statistics
-        200; // match_history (variable size, estimate)
+        4 + (TeamMatchResult::LEN * MAX_MATCH_HISTORY) + // match_history
+        4 + (11 * MAX_SYNERGY_PAIRS); // synergy_pairs
 }
 
 // Player position in a team
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct RosterPosition {
     pub player_mint: Pubkey,
-    pub position: String,
+    pub position: Position,
     pub added_at: i64,
 }
 
@@ -50,9 +72,20 @@ pub struct TeamStatistics {
     pub avg_mechanical: u8,
     pub avg_game_knowledge: u8,
     pub avg_team_communication: u8,
+    pub avg_adaptability: u8,
+    pub avg_consistency: u8,
+    pub overall_rating: u8, // Mean of the five stat averages
     pub synergy_score: u8,  // Calculated based on player compatibility
 }
 
+// Chemistry between two rostered players
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SynergyPair {
+    pub pair_key: [u8; 8],      // Truncated hash of both player mints (order independent)
+    pub matches_together: u16,  // Recorded matches both players were rostered for
+    pub synergy: u8,            // 0-100
+}
+
 // Match result for team
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TeamMatchResult {
@@ -64,6 +97,49 @@ pub struct TeamMatchResult {
     pub tournament_id: Option<Pubkey>,
 }
 
+impl TeamMatchResult {
+    pub const LEN: usize =
+        4 + MAX_MATCH_ID_LEN + // match_id
+        8 + // timestamp
+        32 + // opponent
+        1 + // win
+        2 + // score
+        33; // tournament_id (Option<Pubkey>)
+}
+
+// Team account layout before the current one (positions were free-form strings)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyTeamAccount {
+    pub owner: Pubkey,
+    pub name: String,
+    pub collection_mint: Option<Pubkey>,
+    pub logo_uri: String,
+    pub created_at: i64,
+    pub last_updated: i64,
+    pub roster: Vec<LegacyRosterPosition>,
+    pub statistics: LegacyTeamStatistics,
+    pub match_history: Vec<TeamMatchResult>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRosterPosition {
+    pub player_mint: Pubkey,
+    pub position: String,
+    pub added_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyTeamStatistics {
+    pub matches_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub tournament_wins: u32,
+    pub avg_mechanical: u8,
+    pub avg_game_knowledge: u8,
+    pub avg_team_communication: u8,
+    pub synergy_score: u8,
+}
+
 // Context for creating a team
 #[derive(Accounts)]
 #[instruction(name: String, logo_uri: String)] // Add this line to access instruction parameters
@@ -102,7 +178,9 @@ pub struct AddPlayerToTeam<'info> {
         mut,
         seeds = [b"player", player_mint.key().as_ref()],
         bump,
-        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam
+        constraint = player_account.team.is_none() @ ErrorCode::PlayerAlreadyOnTeam,
+        constraint = !player_account.retired @ ErrorCode::PlayerRetired,
+        constraint = !player_account.escrowed @ ErrorCode::PlayerEscrowed
     )]
     pub player_account: Account<'info, PlayerAccount>,
     
@@ -111,6 +189,17 @@ pub struct AddPlayerToTeam<'info> {
     constraint = player_account.mint == player_mint.key()
 )]
 pub player_mint: Account<'info, anchor_spl::token::Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PlayerContract::LEN,
+        seeds = [b"contract", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 // Context for removing a player from a team
@@ -137,6 +226,52 @@ pub struct RemovePlayerFromTeam<'info> {
     
     /// The mint of the player NFT
     pub player_mint: Account<'info, Mint>,
+    
+    // Releasing a player early tears up their contract
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"contract", team_account.key().as_ref(), player_account.key().as_ref()],
+        bump
+    )]
+    pub player_contract: Account<'info, PlayerContract>,
+}
+
+// Context for closing an empty team
+#[derive(Accounts)]
+pub struct CloseTeam<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"team", owner.key().as_ref(), team_account.name.as_bytes()],
+        bump,
+        constraint = team_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = team_account.roster.is_empty() @ ErrorCode::TeamNotEmpty
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+}
+
+// Context for moving a legacy team account to the current layout
+#[derive(Accounts)]
+pub struct MigrateTeamAccount<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Legacy team account, validated and rewritten in migrate_team_account
+    #[account(mut, owner = crate::ID)]
+    pub team_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Context for recomputing team statistics from the roster (permissionless)
+#[derive(Accounts)]
+pub struct RefreshTeamStats<'info> {
+    #[account(mut)]
+    pub team_account: Account<'info, TeamAccount>,
 }
 
 // Modify the create_team function to process name
@@ -159,6 +294,7 @@ pub fn create_team(
     team_account.roster = Vec::new();
     team_account.statistics = TeamStatistics::default();
     team_account.match_history = Vec::new();
+    team_account.synergy_pairs = Vec::new();
     
     Ok(())
 }
@@ -167,37 +303,72 @@ pub fn create_team(
 pub fn add_player_to_team(
     ctx: Context<AddPlayerToTeam>,
     player_mint: Pubkey,
-    position: String,
+    position: Position,
+    salary_per_period: u64,
+    contract_periods: u16,
 ) -> Result<()> {
-    let team_account = &mut ctx.accounts.team_account;
-    let player_account = &mut ctx.accounts.player_account;
     let clock = Clock::get()?;
     
-    // Check if team roster is full (max 5 players for this example)
-    if team_account.roster.len() >= 5 {
-        return Err(ErrorCode::TeamRosterFull.into());
-    }
-    
-    // Check if this position is already filled
-    if team_account.roster.iter().any(|p| p.position == position) {
-        return Err(ErrorCode::PositionAlreadyFilled.into());
-    }
+    // Only whoever holds the use rights (owner, or renter while rented) can roster the player
+    require_keys_eq!(
+        ctx.accounts.player_account.controller(clock.unix_timestamp),
+        ctx.accounts.owner.key(),
+        ErrorCode::UnauthorizedAccess
+    );
     
-    // Add player to team roster
-    team_account.roster.push(RosterPosition {
+    join_team(
+        &mut ctx.accounts.team_account,
+        &mut ctx.accounts.player_account,
+        &mut ctx.accounts.player_contract,
         player_mint,
-        position: position.clone(),
-        added_at: clock.unix_timestamp,
-    });
+        SigningTerms { position, salary_per_period, contract_periods },
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+    )
+}
+
+// Roster position and contract a player signs on with
+pub struct SigningTerms {
+    pub position: Position,
+    pub salary_per_period: u64,
+    pub contract_periods: u16,
+}
+
+// Shared roster logic for signing a player (direct adds and accepted offers)
+// `roster_accounts` are the players already on the roster, used to refresh team statistics
+pub fn join_team(
+    team_account: &mut Account<TeamAccount>,
+    player_account: &mut Account<PlayerAccount>,
+    player_contract: &mut PlayerContract,
+    player_mint: Pubkey,
+    terms: SigningTerms,
+    roster_accounts: &[AccountInfo],
+    now: i64,
+) -> Result<()> {
+    // Add player to team roster
+    add_to_roster(team_account, player_mint, terms.position, now)?;
     
     // Update player's team reference
     player_account.team = Some(team_account.key());
+    safe_update_stat(&mut player_account.morale, MORALE_ROSTERED);
+    
+    // Sign the player to a contract paid from the team treasury
+    sign_player_contract(
+        player_contract,
+        team_account.key(),
+        player_account,
+        player_account.key(),
+        terms.salary_per_period,
+        terms.contract_periods,
+        now,
+    )?;
     
     // Update team statistics based on new player addition
-    update_team_statistics(team_account);
+    let team_key = team_account.key();
+    refresh_roster_statistics(team_account, team_key, roster_accounts, Some(player_account))?;
     
     // Update last updated timestamp
-    team_account.last_updated = clock.unix_timestamp;
+    team_account.last_updated = now;
     
     Ok(())
 }
@@ -211,20 +382,22 @@ pub fn remove_player_from_team(
     let player_account = &mut ctx.accounts.player_account;
     let clock = Clock::get()?;
     
-    // Find player index in roster
-    let player_index = team_account.roster
-        .iter()
-        .position(|p| p.player_mint == player_mint)
-        .ok_or(ErrorCode::PlayerNotOnTeam)?;
+    // Wages already earned must be paid before a release
+    require!(
+        ctx.accounts.player_contract.periods_due(clock.unix_timestamp) == 0,
+        ErrorCode::SalaryOutstanding
+    );
     
     // Remove player from roster
-    team_account.roster.remove(player_index);
+    remove_from_roster(team_account, &player_mint)?;
     
-    // Clear player's team reference
+    // Clear player's team reference (being benched hurts morale)
     player_account.team = None;
+    safe_update_stat(&mut player_account.morale, MORALE_BENCHED);
     
-    // Update team statistics
-    update_team_statistics(team_account);
+    // Update team statistics from the players still on the roster
+    let team_key = team_account.key();
+    refresh_roster_statistics(team_account, team_key, ctx.remaining_accounts, None)?;
     
     // Update last updated timestamp
     team_account.last_updated = clock.unix_timestamp;
@@ -232,79 +405,429 @@ pub fn remove_player_from_team(
     Ok(())
 }
 
-// Helper function to update team statistics based on current roster
-fn update_team_statistics(team: &mut TeamAccount) {
-    // In a real implementation, this would fetch all player accounts
-    // and calculate aggregate statistics
+// Recompute team statistics; every rostered player must be passed as a remaining account
+pub fn refresh_team_stats(ctx: Context<RefreshTeamStats>) -> Result<()> {
+    let team_account = &mut ctx.accounts.team_account;
     
-    // For this example, we'll just set some placeholder values
-    // In a real implementation, you would use a CPI to get player stats
-    let roster_size = team.roster.len();
-    if roster_size > 0 {
-        team.statistics.avg_mechanical = 70;
-        team.statistics.avg_game_knowledge = 65;
-        team.statistics.avg_team_communication = 75;
-        
-        // Calculate synergy score based on time played together
-        // Teams with players who have been together longer have better synergy
-        let current_time = Clock::get().unwrap().unix_timestamp;
-        let avg_time_together = team.roster.iter()
-            .map(|p| current_time - p.added_at)
-            .sum::<i64>() / roster_size as i64;
+    let team_key = team_account.key();
+    refresh_roster_statistics(team_account, team_key, ctx.remaining_accounts, None)?;
+    
+    msg!("Team rating: {}", team_account.statistics.overall_rating);
+    
+    Ok(())
+}
+
+// Close a team once its roster is empty, returning rent to the owner
+pub fn close_team(_ctx: Context<CloseTeam>) -> Result<()> {
+    msg!("Team closed");
+    
+    Ok(())
+}
+
+// Helper to put a player into an open roster position
+pub fn add_to_roster(team: &mut TeamAccount, player_mint: Pubkey, position: Position, now: i64) -> Result<()> {
+    // Check if team roster is full (max 5 players for this example)
+    if team.roster.len() >= 5 {
+        return Err(ErrorCode::TeamRosterFull.into());
+    }
+    
+    // Check if this position is already filled
+    if team.roster.iter().any(|p| p.position == position) {
+        return Err(ErrorCode::PositionAlreadyFilled.into());
+    }
+    
+    team.roster.push(RosterPosition {
+        player_mint,
+        position,
+        added_at: now,
+    });
+    
+    Ok(())
+}
+
+// Helper to drop a player from the roster
+pub fn remove_from_roster(team: &mut TeamAccount, player_mint: &Pubkey) -> Result<()> {
+    let player_index = team.roster
+        .iter()
+        .position(|p| p.player_mint == *player_mint)
+        .ok_or(ErrorCode::PlayerNotOnTeam)?;
+    
+    team.roster.remove(player_index);
+    
+    Ok(())
+}
+
+// Whether a rostered player takes the field for the team
+// Players out on loan keep their parent roster slot but only play for the borrowing team
+pub fn fields_for_team(player: &PlayerAccount, team_key: Pubkey) -> bool {
+    player.team == Some(team_key)
+}
+
+// Load rostered players passed in through remaining accounts, checking each against the roster
+// Players out on loan still hold their parent roster slot and count for the parent team's statistics
+pub fn load_roster_players(
+    team: &TeamAccount,
+    team_key: Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<Vec<PlayerAccount>> {
+    let mut players: Vec<PlayerAccount> = Vec::with_capacity(accounts.len());
+    
+    for account_info in accounts {
+        let player = load_player_account(account_info)?;
         
-        // Convert to days and cap at 30 days for max synergy
-        let days_together = (avg_time_together / 86400) as u8;
-        let base_synergy = 60u8;
-        let time_bonus = std::cmp::min(20, days_together / 2);
+        require!(
+            (player.team == Some(team_key) || player.loaned_from == Some(team_key)) &&
+                is_player_on_team(team, &player.mint),
+            ErrorCode::InvalidRosterAccounts
+        );
+        require!(
+            !players.iter().any(|p| p.mint == player.mint),
+            ErrorCode::InvalidRosterAccounts
+        );
         
-        team.statistics.synergy_score = base_synergy + time_bonus;
-    } else {
+        players.push(player);
+    }
+    
+    Ok(players)
+}
+
+// Recompute team statistics after any roster change
+// `roster_accounts` are the players on the roster other than `joined`, a player who just signed
+pub fn refresh_roster_statistics(
+    team: &mut TeamAccount,
+    team_key: Pubkey,
+    roster_accounts: &[AccountInfo],
+    joined: Option<&PlayerAccount>,
+) -> Result<()> {
+    let mut players = load_roster_players(team, team_key, roster_accounts)?;
+    if let Some(player) = joined {
+        players.push(player.clone());
+    }
+    
+    update_team_statistics(team, &players)
+}
+
+// Recompute team statistics from the rostered players (must cover the whole roster)
+fn update_team_statistics(team: &mut TeamAccount, players: &[PlayerAccount]) -> Result<()> {
+    require!(players.len() == team.roster.len(), ErrorCode::InvalidRosterAccounts);
+    
+    let roster_size = players.len();
+    if roster_size == 0 {
         team.statistics.avg_mechanical = 0;
         team.statistics.avg_game_knowledge = 0;
         team.statistics.avg_team_communication = 0;
+        team.statistics.avg_adaptability = 0;
+        team.statistics.avg_consistency = 0;
+        team.statistics.overall_rating = 0;
         team.statistics.synergy_score = 0;
+        team.synergy_pairs = Vec::new();
+        return Ok(());
     }
+    
+    let average = |stat: fn(&PlayerAccount) -> u8| {
+        (players.iter().map(|p| stat(p) as u32).sum::<u32>() / roster_size as u32) as u8
+    };
+    
+    team.statistics.avg_mechanical = average(|p| p.mechanical);
+    team.statistics.avg_game_knowledge = average(|p| p.game_knowledge);
+    team.statistics.avg_team_communication = average(|p| p.team_communication);
+    team.statistics.avg_adaptability = average(|p| p.adaptability);
+    team.statistics.avg_consistency = average(|p| p.consistency);
+    
+    team.statistics.overall_rating = ((
+        team.statistics.avg_mechanical as u16 +
+        team.statistics.avg_game_knowledge as u16 +
+        team.statistics.avg_team_communication as u16 +
+        team.statistics.avg_adaptability as u16 +
+        team.statistics.avg_consistency as u16
+    ) / 5) as u8;
+    
+    // Pairs that are no longer together drop out; history of the rest is kept
+    team.statistics.synergy_score = update_synergy_pairs(team, players, None);
+    
+    Ok(())
+}
+
+// Order-independent key for a pair of players
+pub fn synergy_pair_key(a: &Pubkey, b: &Pubkey) -> [u8; 8] {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let hash = hashv(&[first.as_ref(), second.as_ref()]).to_bytes();
+    
+    let mut key = [0u8; 8];
+    key.copy_from_slice(&hash[..8]);
+    key
+}
+
+// How naturally two roles play off each other (0-20)
+pub fn position_complementarity(a: Position, b: Position) -> u8 {
+    use Position::*;
+    
+    match (a, b) {
+        (Adc, Support) | (Support, Adc) => 20,       // Bot lane duo
+        (Jungle, Mid) | (Mid, Jungle) => 16,
+        (Jungle, Top) | (Top, Jungle) => 14,
+        (Jungle, Support) | (Support, Jungle) => 12, // Roaming and vision
+        (Mid, Support) | (Support, Mid) => 10,
+        (Flex, _) | (_, Flex) => 8,
+        _ => 5,
+    }
+}
+
+// Synergy of one pair (0-100): up to 50 from matches played together, up to 20 from
+// how their roster roles fit and up to 30 from their combined team communication
+pub fn pair_synergy(
+    a: &PlayerAccount,
+    a_role: Position,
+    b: &PlayerAccount,
+    b_role: Position,
+    matches_together: u16,
+) -> u8 {
+    let history = std::cmp::min(matches_together, SYNERGY_HISTORY_CAP) as u32 * 50 / SYNERGY_HISTORY_CAP as u32;
+    let complementarity = position_complementarity(a_role, b_role) as u32;
+    let communication = (a.team_communication as u32 + b.team_communication as u32) * 30 / 200;
+    
+    std::cmp::min(100, history + complementarity + communication) as u8
+}
+
+// Rebuild the pair table for the current roster (crediting a shared match if one was just
+// played) and return the roster's average pair synergy
+// `shared_match` is the team whose lineup just played together, crediting the pairs that took the field
+fn update_synergy_pairs(team: &mut TeamAccount, players: &[PlayerAccount], shared_match: Option<Pubkey>) -> u8 {
+    let role = |player: &PlayerAccount| {
+        team.roster.iter()
+            .find(|r| r.player_mint == player.mint)
+            .map_or(player.position, |r| r.position)
+    };
+    
+    let mut pairs: Vec<SynergyPair> = Vec::with_capacity(MAX_SYNERGY_PAIRS);
+    for (i, a) in players.iter().enumerate() {
+        for b in players[i + 1..].iter() {
+            let pair_key = synergy_pair_key(&a.mint, &b.mint);
+            let previous = team.synergy_pairs.iter()
+                .find(|p| p.pair_key == pair_key)
+                .map_or(0, |p| p.matches_together);
+            let played = shared_match.is_some_and(|key| fields_for_team(a, key) && fields_for_team(b, key));
+            let matches_together = if played { previous.saturating_add(1) } else { previous };
+            
+            pairs.push(SynergyPair {
+                pair_key,
+                matches_together,
+                synergy: pair_synergy(a, role(a), b, role(b), matches_together),
+            });
+        }
+    }
+    
+    let average = if pairs.is_empty() {
+        0
+    } else {
+        (pairs.iter().map(|p| p.synergy as u32).sum::<u32>() / pairs.len() as u32) as u8
+    };
+    
+    team.synergy_pairs = pairs;
+    average
+}
+
+// Lineup strength used for match resolution (0-100 scale)
+// Averages each player's core stats with their active ability bonuses, the
+// lineup's team-wide boosts, morale, hero mastery and off-role penalties, then
+// scales the result by form
+pub fn calculate_lineup_strength(
+    players: &[PlayerAccount],
+    roster: &[RosterPosition],
+    situation: &MatchSituation,
+    definitions: &[AbilityDefinition],
+    now: i64,
+) -> u8 {
+    if players.is_empty() {
+        return 0;
+    }
+    
+    let modifiers: Vec<AbilityModifiers> = players.iter()
+        .map(|p| calculate_ability_modifiers(p, situation, definitions))
+        .collect();
+    
+    // Team-wide boosts from every player stack, but are capped per stat
+    let mut team_boost = [0u16; 5];
+    for m in modifiers.iter() {
+        for (boost, bonus) in team_boost.iter_mut().zip(m.team.iter()) {
+            *boost = std::cmp::min(MAX_ABILITY_BONUS, *boost + *bonus as u16);
+        }
+    }
+    
+    let total: u32 = players.iter()
+        .zip(modifiers.iter())
+        .map(|(player, m)| {
+            let morale = player.morale_modifier() as i16;
+            // Assume each player drafts their comfort pick
+            let hero_bonus = player.game_specific_data.comfort_pick()
+                .map_or(0, |h| h.mastery / 20) as i16;
+            let role_penalty = roster.iter()
+                .find(|r| r.player_mint == player.mint)
+                .map_or(0, |r| player.position.role_penalty(&r.position)) as i16;
+            let stat_sum: u32 = player.core_stats().iter()
+                .zip(m.player.iter())
+                .zip(team_boost.iter())
+                .map(|((stat, bonus), team)| {
+                    (*stat as i16 + *bonus as i16 + *team as i16 + morale + hero_bonus - role_penalty).clamp(0, 100) as u32
+                })
+                .sum();
+            
+            // Form swings a player's output between 75% and 100%
+            let form = std::cmp::min(100, player.current_form(now) as u32 + m.form as u32);
+            (stat_sum / 5) * (75 + form / 4) / 100
+        })
+        .sum();
+    
+    (total / players.len() as u32) as u8
 }
 
 // Record a new match result for the team
+// `players` is the full roster; every pair that took the field is credited with a shared match
 pub fn record_team_match_result(
     team: &mut TeamAccount,
-    match_id: String,
-    opponent: Pubkey,
-    win: bool,
-    score: [u8; 2],
-    tournament_id: Option<Pubkey>,
+    team_key: Pubkey,
+    players: &[PlayerAccount],
+    result: TeamMatchResult,
 ) -> Result<()> {
     let clock = Clock::get()?;
     
+    require!(players.len() == team.roster.len(), ErrorCode::InvalidRosterAccounts);
+    require!(result.match_id.len() <= MAX_MATCH_ID_LEN, ErrorCode::InvalidMatchId);
+    
     // Update team statistics
     team.statistics.matches_played += 1;
-    if win {
+    if result.win {
         team.statistics.wins += 1;
     } else {
         team.statistics.losses += 1;
     }
     
-    // Add match to history (keeping only the most recent 10 matches)
-    team.match_history.push(TeamMatchResult {
-        match_id,
-        timestamp: clock.unix_timestamp,
-        opponent,
-        win,
-        score,
-        tournament_id,
-    });
+    // Add match to history (keeping only the most recent matches)
+    team.match_history.push(result);
     
-    if team.match_history.len() > 10 {
+    if team.match_history.len() > MAX_MATCH_HISTORY {
         team.match_history.remove(0);
     }
     
+    // Lineups that stay together build chemistry
+    team.statistics.synergy_score = update_synergy_pairs(team, players, Some(team_key));
+    
     // Update last updated timestamp
     team.last_updated = clock.unix_timestamp;
     
     Ok(())
 }
 
+// Grow a legacy team account and rewrite it in the current layout
+// Stat averages start at zero until the next refresh; synergy builds up from here
+pub fn migrate_team_account(ctx: Context<MigrateTeamAccount>) -> Result<()> {
+    let team_info = ctx.accounts.team_account.to_account_info();
+    require!(team_info.data_len() == LEGACY_TEAM_ACCOUNT_LEN, ErrorCode::TeamAlreadyMigrated);
+    
+    let legacy = {
+        let data = team_info.try_borrow_data()?;
+        require!(
+            data[..8] == TeamAccount::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyTeamAccount::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(legacy.owner, ctx.accounts.owner.key(), ErrorCode::UnauthorizedAccess);
+    
+    // The owner covers the rent for the larger account
+    let rent = Rent::get()?.minimum_balance(TeamAccount::LEN);
+    let shortfall = rent.saturating_sub(team_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: team_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    // Legacy positions were case-sensitive, so two entries can map onto the same role:
+    // a repeat moves to Flex, and the migration is rejected if Flex is taken too
+    let mut roster: Vec<RosterPosition> = Vec::with_capacity(legacy.roster.len());
+    for entry in legacy.roster.into_iter() {
+        let mut position = legacy_position(&entry.position);
+        if roster.iter().any(|p| p.position == position) {
+            position = Position::Flex;
+        }
+        require!(roster.iter().all(|p| p.position != position), ErrorCode::PositionAlreadyFilled);
+        
+        roster.push(RosterPosition {
+            player_mint: entry.player_mint,
+            position,
+            added_at: entry.added_at,
+        });
+    }
+    
+    team_info.realloc(TeamAccount::LEN, true)?;
+    
+    let skip = legacy.match_history.len().saturating_sub(MAX_MATCH_HISTORY);
+    let team = TeamAccount {
+        owner: legacy.owner,
+        name: legacy.name,
+        collection_mint: legacy.collection_mint,
+        logo_uri: legacy.logo_uri,
+        created_at: legacy.created_at,
+        last_updated: Clock::get()?.unix_timestamp,
+        roster,
+        statistics: TeamStatistics {
+            matches_played: legacy.statistics.matches_played,
+            wins: legacy.statistics.wins,
+            losses: legacy.statistics.losses,
+            tournament_wins: legacy.statistics.tournament_wins,
+            avg_mechanical: legacy.statistics.avg_mechanical,
+            avg_game_knowledge: legacy.statistics.avg_game_knowledge,
+            avg_team_communication: legacy.statistics.avg_team_communication,
+            avg_adaptability: 0,
+            avg_consistency: 0,
+            overall_rating: 0,
+            synergy_score: legacy.statistics.synergy_score,
+        },
+        match_history: legacy.match_history.into_iter().skip(skip).map(|mut result| {
+            truncate_match_id(&mut result.match_id);
+            result
+        }).collect(),
+        synergy_pairs: Vec::new(),
+    };
+    
+    let mut data = team_info.try_borrow_mut_data()?;
+    team.try_serialize(&mut &mut data[..])?;
+    
+    Ok(())
+}
+
+// Map a legacy free-form position string onto a role (unknown strings become Flex)
+pub fn legacy_position(position: &str) -> Position {
+    match position.to_ascii_lowercase().as_str() {
+        "top" => Position::Top,
+        "jungle" | "jungler" => Position::Jungle,
+        "mid" | "middle" => Position::Mid,
+        "adc" | "bot" | "carry" => Position::Adc,
+        "support" => Position::Support,
+        _ => Position::Flex,
+    }
+}
+
+// Cut a match id down to MAX_MATCH_ID_LEN bytes on a character boundary
+fn truncate_match_id(match_id: &mut String) {
+    if match_id.len() <= MAX_MATCH_ID_LEN {
+        return;
+    }
+    
+    let mut end = MAX_MATCH_ID_LEN;
+    while !match_id.is_char_boundary(end) {
+        end -= 1;
+    }
+    match_id.truncate(end);
+}
+
 // Helper to check if a player is on a specific team
 pub fn is_player_on_team(team: &TeamAccount, player_mint: &Pubkey) -> bool {
     team.roster.iter().any(|p| p.player_mint == *player_mint)
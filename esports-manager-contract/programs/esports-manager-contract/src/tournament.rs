@@ -1,6 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::ability::{AbilityDefinition, MatchSituation};
 use crate::errors::ErrorCode;
-use crate::team::TeamAccount;
+use crate::player::{load_player_account, PlayerAccount, MAX_PLAYING_AGE};
+use crate::team::{
+    calculate_lineup_strength, fields_for_team, is_player_on_team, load_roster_players, record_team_match_result,
+    TeamAccount, TeamMatchResult, MAX_MATCH_ID_LEN,
+};
+use crate::utils::move_lamports;
 
 // Tournament Account Structure
 #[account]
@@ -12,15 +19,22 @@ pub struct TournamentAccount {
     pub start_time: i64,
     pub end_time: Option<i64>,
     pub max_teams: u8,
-    pub registered_teams: Vec<Pubkey>,
+    pub registered_teams: Vec<Pubkey>, // Strongest lineup first, so the bracket is seeded
     pub matches: Vec<TournamentMatch>,
     pub status: TournamentStatus,
     pub created_at: i64,
+    pub team_strengths: Vec<u8>, // Lineup strength at registration, then in the team's latest match; parallel to registered_teams
+    pub prize_claimed: bool, // Set once the champion has claimed, even when the pool was empty
 }
 
-// Fixed size for account allocation
+// Largest bracket a tournament account can hold (keeps it under the 10KB allocation limit)
+pub const MAX_TOURNAMENT_TEAMS: u8 = 32;
+
+// Account size for a bracket of `max_teams`; a knockout bracket never has more matches than teams
 impl TournamentAccount {
-    pub const LEN: usize = 
+    pub fn space(max_teams: u8) -> usize {
+        let max_teams = std::cmp::min(max_teams, MAX_TOURNAMENT_TEAMS) as usize;
+        
         8 + // discriminator
         32 + // authority pubkey
         36 + // name (max 32 chars)
@@ -29,10 +43,13 @@ impl TournamentAccount {
         8 + // start_time
         9 + // end_time (Option<i64>)
         1 + // max_teams
-        64 + // registered_teams (variable size, estimate)
-        200 + // matches (variable size, estimate)
+        4 + 32 * max_teams + // registered_teams
+        4 + TournamentMatch::LEN * max_teams + // matches
         1 + // status
-        8; // created_at
+        8 + // created_at
+        4 + max_teams + // team_strengths
+        1 // prize_claimed
+    }
 }
 
 // Tournament match data
@@ -48,6 +65,18 @@ pub struct TournamentMatch {
     pub completed: bool,
 }
 
+impl TournamentMatch {
+    pub const LEN: usize =
+        4 + MAX_MATCH_ID_LEN + // match_id
+        8 + // timestamp
+        32 + // team_a
+        32 + // team_b
+        33 + // winner (Option<Pubkey>)
+        2 + // score
+        1 + // round
+        1; // completed
+}
+
 // Tournament status enum
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum TournamentStatus {
@@ -67,7 +96,7 @@ pub struct CreateTournament<'info> {
     #[account(
         init,
         payer = authority,
-        space = TournamentAccount::LEN,
+        space = TournamentAccount::space(max_teams),
         seeds = [b"tournament", authority.key().as_ref(), name.as_bytes()],
         bump
     )]
@@ -123,6 +152,82 @@ pub struct RecordMatchResult<'info> {
     pub loser_team: Account<'info, TeamAccount>,
 }
 
+// Context for the champion claiming the prize pool
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = team_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        constraint = tournament_account.status == TournamentStatus::Completed @ ErrorCode::TournamentNotFinished,
+        constraint = !tournament_account.prize_claimed @ ErrorCode::PrizeAlreadyClaimed
+    )]
+    pub tournament_account: Account<'info, TournamentAccount>,
+}
+
+// Context for calling off a tournament that hasn't finished
+#[derive(Accounts)]
+pub struct CancelTournament<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = tournament_account.authority == authority.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = (tournament_account.status == TournamentStatus::Registration ||
+            tournament_account.status == TournamentStatus::InProgress) @ ErrorCode::TournamentAlreadyFinished
+    )]
+    pub tournament_account: Account<'info, TournamentAccount>,
+}
+
+// Context for a registered team taking its entry fee back from a canceled tournament
+#[derive(Accounts)]
+pub struct ClaimEntryRefund<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        constraint = team_account.owner == owner.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub team_account: Account<'info, TeamAccount>,
+    
+    #[account(
+        mut,
+        constraint = tournament_account.status == TournamentStatus::Canceled @ ErrorCode::TournamentNotCanceled
+    )]
+    pub tournament_account: Account<'info, TournamentAccount>,
+}
+
+// Context for closing a finished tournament
+#[derive(Accounts)]
+pub struct CloseTournament<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"tournament", authority.key().as_ref(), tournament_account.name.as_bytes()],
+        bump,
+        constraint = tournament_account.authority == authority.key() @ ErrorCode::UnauthorizedAccess,
+        constraint = (tournament_account.status == TournamentStatus::Completed ||
+            tournament_account.status == TournamentStatus::Canceled) @ ErrorCode::TournamentNotFinished,
+        // The champion must have claimed, and refunds are paid out of the pool, so anything left is unclaimed
+        constraint = (if tournament_account.status == TournamentStatus::Completed {
+            tournament_account.prize_claimed
+        } else {
+            tournament_account.prize_pool == 0
+        }) @ ErrorCode::PrizesUnclaimed
+    )]
+    pub tournament_account: Account<'info, TournamentAccount>,
+}
+
 // Create a new tournament
 pub fn create_tournament(
     ctx: Context<CreateTournament>,
@@ -135,7 +240,7 @@ pub fn create_tournament(
     let clock = Clock::get()?;
     
     // Validate tournament parameters
-    if max_teams < 2 || max_teams > 64 {
+    if !(2..=MAX_TOURNAMENT_TEAMS).contains(&max_teams) {
         return Err(ErrorCode::InvalidTournamentParameters.into());
     }
     
@@ -155,13 +260,15 @@ pub fn create_tournament(
     tournament_account.matches = Vec::new();
     tournament_account.status = TournamentStatus::Registration;
     tournament_account.created_at = clock.unix_timestamp;
+    tournament_account.team_strengths = Vec::new();
+    tournament_account.prize_claimed = false;
     
     Ok(())
 }
 
 // Register a team for a tournament
-pub fn register_team(
-    ctx: Context<RegisterTeamForTournament>,
+pub fn register_team<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RegisterTeamForTournament<'info>>,
     tournament_id: Pubkey,
     team_id: Pubkey,
 ) -> Result<()> {
@@ -179,10 +286,47 @@ pub fn register_team(
         ErrorCode::InvalidTeamId
     );
     
-    // Add team to tournament
-    tournament_account.registered_teams.push(team_account.key());
+    // Rostered players are passed as remaining accounts and must all be available;
+    // any accounts after them are catalog definitions for the lineup's abilities
+    let clock = Clock::get()?;
+    require!(ctx.remaining_accounts.len() >= team_account.roster.len(), ErrorCode::InvalidLineup);
+    let (lineup, definition_accounts) = ctx.remaining_accounts.split_at(team_account.roster.len());
+    let players = verify_tournament_lineup(team_account, lineup, clock.unix_timestamp)?;
+    let definitions = load_ability_definitions(definition_accounts)?;
+    
+    // Seed the team by lineup strength (abilities, morale, hero mastery and roles included),
+    // evaluated at kick-off: early game with the score level
+    let strength = calculate_lineup_strength(
+        &players,
+        &team_account.roster,
+        &MatchSituation { late_game: false, team_score: 0, opponent_score: 0 },
+        &definitions,
+        clock.unix_timestamp,
+    );
+    let seed = tournament_account.team_strengths
+        .iter()
+        .position(|s| strength > *s)
+        .unwrap_or(tournament_account.team_strengths.len());
     
-    // Add entry fee to prize pool
+    // Add team to tournament
+    tournament_account.registered_teams.insert(seed, team_account.key());
+    tournament_account.team_strengths.insert(seed, strength);
+    
+    msg!("Team registered with lineup strength {} (seed {})", strength, seed + 1);
+    
+    // Entry fee is held in the tournament account as the prize pool
+    if tournament_account.entry_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: tournament_account.to_account_info(),
+                },
+            ),
+            tournament_account.entry_fee,
+        )?;
+    }
     tournament_account.prize_pool += tournament_account.entry_fee;
     
     // If tournament is full, generate initial matches
@@ -194,8 +338,9 @@ pub fn register_team(
 }
 
 // Record a match result in the tournament
-pub fn record_match_result(
-    ctx: Context<RecordMatchResult>,
+// (pass both rosters, winner first, then any catalog ability definitions either lineup uses)
+pub fn record_match_result<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecordMatchResult<'info>>,
     match_id: String,
     winner_id: Pubkey,
     loser_id: Pubkey,
@@ -203,8 +348,9 @@ pub fn record_match_result(
     match_data: Vec<u8>,
 ) -> Result<()> {
     let tournament_account = &mut ctx.accounts.tournament_account;
-    let winner_team = &ctx.accounts.winner_team;
-    let loser_team = &ctx.accounts.loser_team;
+    let tournament_key = tournament_account.key();
+    let winner_team = &mut ctx.accounts.winner_team;
+    let loser_team = &mut ctx.accounts.loser_team;
     
     // Verify that the provided IDs match the accounts
     require!(
@@ -241,6 +387,69 @@ pub fn record_match_result(
     tournament_match.timestamp = clock.unix_timestamp;
     tournament_match.completed = true;
     
+    // Both rosters are passed as remaining accounts, winner's first, then ability definitions
+    require!(
+        ctx.remaining_accounts.len() >= winner_team.roster.len() + loser_team.roster.len(),
+        ErrorCode::InvalidRosterAccounts
+    );
+    let (winner_accounts, rest) = ctx.remaining_accounts.split_at(winner_team.roster.len());
+    let (loser_accounts, definition_accounts) = rest.split_at(loser_team.roster.len());
+    let winner_players = load_roster_players(winner_team, winner_id, winner_accounts)?;
+    let loser_players = load_roster_players(loser_team, loser_id, loser_accounts)?;
+    let definitions = load_ability_definitions(definition_accounts)?;
+    
+    // Players locked into a training program can't have played the match
+    // (players out on loan play for the borrowing team, not their parent)
+    require!(
+        winner_players.iter().filter(|p| fields_for_team(p, winner_id))
+            .chain(loser_players.iter().filter(|p| fields_for_team(p, loser_id)))
+            .all(|p| !p.is_in_training()),
+        ErrorCode::PlayerInTraining
+    );
+    
+    // Score is given winner first
+    record_team_match_result(winner_team, winner_id, &winner_players, TeamMatchResult {
+        match_id: match_id.clone(),
+        timestamp: clock.unix_timestamp,
+        opponent: loser_id,
+        win: true,
+        score,
+        tournament_id: Some(tournament_key),
+    })?;
+    record_team_match_result(loser_team, loser_id, &loser_players, TeamMatchResult {
+        match_id: match_id.clone(),
+        timestamp: clock.unix_timestamp,
+        opponent: winner_id,
+        win: false,
+        score: [score[1], score[0]],
+        tournament_id: Some(tournament_key),
+    })?;
+    
+    // Re-rate both lineups against how the match actually ended: late game, at the final score,
+    // so comeback and closing abilities count
+    let winner_lineup: Vec<PlayerAccount> = winner_players.into_iter().filter(|p| fields_for_team(p, winner_id)).collect();
+    let loser_lineup: Vec<PlayerAccount> = loser_players.into_iter().filter(|p| fields_for_team(p, loser_id)).collect();
+    let winner_strength = calculate_lineup_strength(
+        &winner_lineup,
+        &winner_team.roster,
+        &MatchSituation { late_game: true, team_score: score[0], opponent_score: score[1] },
+        &definitions,
+        clock.unix_timestamp,
+    );
+    let loser_strength = calculate_lineup_strength(
+        &loser_lineup,
+        &loser_team.roster,
+        &MatchSituation { late_game: true, team_score: score[1], opponent_score: score[0] },
+        &definitions,
+        clock.unix_timestamp,
+    );
+    for (team, strength) in [(winner_id, winner_strength), (loser_id, loser_strength)] {
+        if let Some(index) = tournament_account.registered_teams.iter().position(|t| *t == team) {
+            tournament_account.team_strengths[index] = strength;
+        }
+    }
+    msg!("Match lineup strength: {} vs {}", winner_strength, loser_strength);
+    
     // Store match data in the blockchain for later reference
     // In a production system, you would likely have more efficient storage
     msg!("Match {} result recorded: {} vs {}", 
@@ -250,7 +459,7 @@ pub fn record_match_result(
     );
     
     // Check if all matches in the current round are completed
-    let current_round = tournament_match.round;
+    let current_round = tournament_account.matches[match_index].round;
     let all_completed = tournament_account.matches
         .iter()
         .filter(|m| m.round == current_round)
@@ -267,12 +476,123 @@ pub fn record_match_result(
     Ok(())
 }
 
+// Pay the whole prize pool to the team that won the final
+pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+    let tournament_account = &mut ctx.accounts.tournament_account;
+    let team_account = &mut ctx.accounts.team_account;
+    
+    require!(
+        tournament_champion(tournament_account) == Some(team_account.key()),
+        ErrorCode::NotTournamentChampion
+    );
+    
+    let prize = tournament_account.prize_pool;
+    tournament_account.prize_pool = 0;
+    tournament_account.prize_claimed = true;
+    move_lamports(&tournament_account.to_account_info(), &ctx.accounts.owner.to_account_info(), prize)?;
+    
+    team_account.statistics.tournament_wins += 1;
+    
+    msg!("{} claimed {} lamports from {}", team_account.name, prize, tournament_account.name);
+    
+    Ok(())
+}
+
+// Call off a tournament; registered teams can then claim their entry fees back
+pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+    let tournament_account = &mut ctx.accounts.tournament_account;
+    let clock = Clock::get()?;
+    
+    tournament_account.status = TournamentStatus::Canceled;
+    tournament_account.end_time = Some(clock.unix_timestamp);
+    
+    msg!("Tournament {} canceled", tournament_account.name);
+    
+    Ok(())
+}
+
+// Refund a team's entry fee from a canceled tournament (each team can claim once)
+pub fn claim_entry_refund(ctx: Context<ClaimEntryRefund>) -> Result<()> {
+    let tournament_account = &mut ctx.accounts.tournament_account;
+    let team_key = ctx.accounts.team_account.key();
+    
+    let index = tournament_account.registered_teams
+        .iter()
+        .position(|team| *team == team_key)
+        .ok_or(ErrorCode::TeamNotRegistered)?;
+    tournament_account.registered_teams.remove(index);
+    tournament_account.team_strengths.remove(index);
+    
+    let refund = std::cmp::min(tournament_account.entry_fee, tournament_account.prize_pool);
+    tournament_account.prize_pool -= refund;
+    move_lamports(&tournament_account.to_account_info(), &ctx.accounts.owner.to_account_info(), refund)?;
+    
+    Ok(())
+}
+
+// Close a completed or canceled tournament, returning rent to the authority
+pub fn close_tournament(_ctx: Context<CloseTournament>) -> Result<()> {
+    msg!("Tournament closed");
+    
+    Ok(())
+}
+
+// Helper to load the catalog ability definitions passed in through remaining accounts
+fn load_ability_definitions<'info>(accounts: &'info [AccountInfo<'info>]) -> Result<Vec<AbilityDefinition>> {
+    accounts.iter()
+        .map(|info| Ok(Account::<AbilityDefinition>::try_from(info)?.into_inner()))
+        .collect()
+}
+
+// Helper to verify a team's full roster is present and able to play, returning the lineup
+// (players out on loan are left out of the parent team's lineup)
+fn verify_tournament_lineup(
+    team: &Account<TeamAccount>,
+    lineup: &[AccountInfo],
+    now: i64,
+) -> Result<Vec<PlayerAccount>> {
+    require!(lineup.len() == team.roster.len(), ErrorCode::InvalidLineup);
+    
+    let mut players: Vec<PlayerAccount> = Vec::with_capacity(lineup.len());
+    let mut loaned_out: Vec<PlayerAccount> = Vec::new();
+    for account_info in lineup {
+        let player = load_player_account(account_info)?;
+        
+        require!(
+            (player.team == Some(team.key()) || player.loaned_from == Some(team.key())) &&
+                is_player_on_team(team, &player.mint),
+            ErrorCode::InvalidLineup
+        );
+        require!(
+            players.iter().chain(loaned_out.iter()).all(|p| p.mint != player.mint),
+            ErrorCode::InvalidLineup
+        );
+        // Players out on loan keep their slot but only play for the borrowing team
+        if !fields_for_team(&player, team.key()) {
+            loaned_out.push(player);
+            continue;
+        }
+        // A rented player can only be fielded by the renter, and only until the rental expires
+        if player.user.is_some() {
+            require!(player.active_user(now) == Some(team.owner), ErrorCode::PlayerRented);
+        }
+        require!(!player.is_in_training(), ErrorCode::PlayerInTraining);
+        require!(!player.is_injured(now), ErrorCode::PlayerInjured);
+        require!(!player.retired, ErrorCode::PlayerRetired);
+        require!(player.age(now) <= MAX_PLAYING_AGE, ErrorCode::PlayerTooOld);
+        
+        players.push(player);
+    }
+    
+    Ok(players)
+}
+
 // Helper function to generate initial tournament matches
 fn generate_tournament_matches(tournament: &mut TournamentAccount) -> Result<()> {
     // Set tournament status to in progress
     tournament.status = TournamentStatus::InProgress;
     
-    // Simple bracket generation (would be more sophisticated in real implementation)
+    // Teams are kept in seed order, so the top seed meets the bottom seed
     let teams = tournament.registered_teams.clone();
     
     for i in 0..(teams.len() / 2) {
@@ -328,6 +648,17 @@ fn generate_next_round_matches(tournament: &mut TournamentAccount, completed_rou
     Ok(())
 }
 
+// Winner of the final, once it has been played
+fn tournament_champion(tournament: &TournamentAccount) -> Option<Pubkey> {
+    let max_round = tournament.matches.iter().map(|m| m.round).max()?;
+    let mut finals = tournament.matches.iter().filter(|m| m.round == max_round);
+    
+    match (finals.next(), finals.next()) {
+        (Some(final_match), None) => final_match.winner,
+        _ => None,
+    }
+}
+
 // Helper function to check if tournament is completed
 fn check_tournament_completion(tournament: &mut TournamentAccount) {
     // Find the highest round
@@ -347,7 +678,5 @@ fn check_tournament_completion(tournament: &mut TournamentAccount) {
     if final_matches.len() == 1 && final_matches[0].completed {
         tournament.status = TournamentStatus::Completed;
         tournament.end_time = Some(Clock::get().unwrap().unix_timestamp);
-        
-        // In a real implementation, distribute prizes here
     }
 }
\ No newline at end of file
@@ -0,0 +1,619 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, InitializeAccount3, Mint, Token, TokenAccount, Transfer};
+use crate::errors::ErrorCode;
+use crate::contract::PlayerContract;
+use crate::marketplace::{pay_from_wallet, release_escrow};
+use crate::player::PlayerAccount;
+use crate::team::{refresh_roster_statistics, remove_from_roster, TeamAccount};
+use crate::utils::move_lamports;
+
+// Most players either side can put into one trade
+pub const MAX_TRADE_PLAYERS: usize = 3;
+
+// Remaining accounts per offered player when proposing: player, mint, proposer token account, escrow
+const PROPOSE_ACCOUNTS_PER_PLAYER: usize = 4;
+
+// Remaining accounts per player when accepting: player, source token account, destination token
+// account, team and contract (pass the program id for both when the player is not rostered)
+// After the player groups come, for each rostered player in order, the players left on their roster
+const ACCEPT_ACCOUNTS_PER_PLAYER: usize = 5;
+
+// Remaining accounts per offered player when canceling: player, escrow, proposer token account
+const CANCEL_ACCOUNTS_PER_PLAYER: usize = 3;
+
+// Trade Proposal Structure (the proposer's players, lamports and tokens are escrowed under this PDA)
+#[account]
+pub struct TradeProposal {
+    pub proposer: Pubkey,
+    pub counterparty: Pubkey,
+    pub trade_id: u64,
+    pub offered_players: Vec<Pubkey>,   // Player PDAs the proposer gives up
+    pub requested_players: Vec<Pubkey>, // Player PDAs the counterparty gives up
+    pub proposer_lamports: u64,
+    pub counterparty_lamports: u64,
+    pub sweetener_mint: Option<Pubkey>,
+    pub proposer_tokens: u64,
+    pub counterparty_tokens: u64,
+    pub created_at: i64,
+}
+
+// Fixed size for account allocation
+impl TradeProposal {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // proposer pubkey
+        32 + // counterparty pubkey
+        8 + // trade_id
+        4 + (32 * MAX_TRADE_PLAYERS) + // offered_players
+        4 + (32 * MAX_TRADE_PLAYERS) + // requested_players
+        8 + // proposer_lamports
+        8 + // counterparty_lamports
+        33 + // sweetener_mint (Option<Pubkey>)
+        8 + // proposer_tokens
+        8 + // counterparty_tokens
+        8; // created_at
+}
+
+// Context for proposing a trade
+#[derive(Accounts)]
+#[instruction(trade_id: u64)]
+pub struct ProposeTrade<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    /// CHECK: Only recorded as the side allowed to accept
+    #[account(constraint = counterparty.key() != proposer.key() @ ErrorCode::InvalidTrade)]
+    pub counterparty: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = TradeProposal::LEN,
+        seeds = [b"trade", proposer.key().as_ref(), trade_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub trade_proposal: Account<'info, TradeProposal>,
+    
+    // Required when either side adds an SPL token sweetener
+    pub sweetener_mint: Option<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        token::authority = proposer
+    )]
+    pub proposer_sweetener_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Escrow for the proposer's tokens, created in the handler at the trade_escrow PDA
+    #[account(mut)]
+    pub sweetener_escrow: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the counterparty accepting a trade
+#[derive(Accounts)]
+pub struct AcceptTrade<'info> {
+    #[account(mut)]
+    pub counterparty: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"trade", proposer.key().as_ref(), trade_proposal.trade_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer @ ErrorCode::UnauthorizedAccess,
+        has_one = counterparty @ ErrorCode::UnauthorizedAccess
+    )]
+    pub trade_proposal: Account<'info, TradeProposal>,
+    
+    /// CHECK: Proposer, receives the counterparty's side and the trade rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub sweetener_escrow: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::authority = counterparty
+    )]
+    pub counterparty_sweetener_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::authority = proposer
+    )]
+    pub proposer_sweetener_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Context for the proposer withdrawing, or the counterparty rejecting, a trade
+#[derive(Accounts)]
+pub struct CancelTrade<'info> {
+    #[account(
+        constraint = caller.key() == trade_proposal.proposer
+            || caller.key() == trade_proposal.counterparty @ ErrorCode::UnauthorizedAccess
+    )]
+    pub caller: Signer<'info>,
+    
+    // Escrowed lamports go back to the proposer with the rent
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"trade", proposer.key().as_ref(), trade_proposal.trade_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer @ ErrorCode::UnauthorizedAccess
+    )]
+    pub trade_proposal: Account<'info, TradeProposal>,
+    
+    /// CHECK: Proposer, gets the escrowed players, tokens and rent back
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub sweetener_escrow: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::authority = proposer
+    )]
+    pub proposer_sweetener_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+// Propose a swap: the offered players (and any lamports or tokens) are escrowed now,
+// the counterparty's side moves when they accept
+pub fn propose_trade<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProposeTrade<'info>>,
+    trade_id: u64,
+    requested_players: Vec<Pubkey>,
+    proposer_lamports: u64,
+    counterparty_lamports: u64,
+    proposer_tokens: u64,
+    counterparty_tokens: u64,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    let offered_count = remaining_accounts.len() / PROPOSE_ACCOUNTS_PER_PLAYER;
+    
+    require!(
+        remaining_accounts.len().is_multiple_of(PROPOSE_ACCOUNTS_PER_PLAYER)
+            && offered_count <= MAX_TRADE_PLAYERS
+            && requested_players.len() <= MAX_TRADE_PLAYERS
+            && offered_count + requested_players.len() > 0,
+        ErrorCode::InvalidTrade
+    );
+    for (i, player) in requested_players.iter().enumerate() {
+        require!(!requested_players[..i].contains(player), ErrorCode::InvalidTrade);
+    }
+    
+    let proposer = ctx.accounts.proposer.to_account_info();
+    let trade_info = ctx.accounts.trade_proposal.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    
+    // Lamport sweetener is held in the trade account itself
+    pay_from_wallet(&system_program, &proposer, &trade_info, proposer_lamports)?;
+    
+    // Token sweetener needs a mint; the proposer's share is escrowed
+    let sweetener_mint = if proposer_tokens > 0 || counterparty_tokens > 0 {
+        let mint = ctx.accounts.sweetener_mint.as_ref().ok_or(ErrorCode::InvalidTradeAccounts)?;
+        Some(mint.key())
+    } else {
+        None
+    };
+    
+    if proposer_tokens > 0 {
+        let (Some(mint), Some(source), Some(escrow)) = (
+            ctx.accounts.sweetener_mint.as_ref(),
+            ctx.accounts.proposer_sweetener_account.as_ref(),
+            ctx.accounts.sweetener_escrow.as_ref(),
+        ) else {
+            return Err(ErrorCode::InvalidTradeAccounts.into());
+        };
+        require_keys_eq!(source.mint, mint.key(), ErrorCode::InvalidTradeAccounts);
+        
+        create_trade_escrow(&trade_info, &escrow.to_account_info(), &mint.to_account_info(), &proposer, &system_program, &token_program)?;
+        token::transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: source.to_account_info(),
+                    to: escrow.to_account_info(),
+                    authority: proposer.clone(),
+                },
+            ),
+            proposer_tokens,
+        )?;
+    }
+    
+    // Escrow each offered player's NFT
+    let mut offered_players = Vec::with_capacity(offered_count);
+    for accounts in remaining_accounts.chunks(PROPOSE_ACCOUNTS_PER_PLAYER) {
+        let player_info = &accounts[0];
+        let mint_info = &accounts[1];
+        let owner_token_info = &accounts[2];
+        let escrow_info = &accounts[3];
+        
+        let mut player = Account::<PlayerAccount>::try_from(player_info)?;
+        let (player_address, _) = Pubkey::find_program_address(&[b"player", player.mint.as_ref()], ctx.program_id);
+        require_keys_eq!(player_info.key(), player_address, ErrorCode::InvalidTradeAccounts);
+        require_keys_eq!(mint_info.key(), player.mint, ErrorCode::InvalidTradeAccounts);
+        require_keys_eq!(player.owner, proposer.key(), ErrorCode::UnauthorizedAccess);
+        require!(player.user.is_none(), ErrorCode::PlayerRented);
+        require!(!player.escrowed, ErrorCode::PlayerEscrowed);
+        require!(!offered_players.contains(&player_info.key()), ErrorCode::InvalidTrade);
+        
+        create_trade_escrow(&trade_info, escrow_info, mint_info, &proposer, &system_program, &token_program)?;
+        token::transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: owner_token_info.clone(),
+                    to: escrow_info.clone(),
+                    authority: proposer.clone(),
+                },
+            ),
+            1,
+        )?;
+        
+        player.escrowed = true;
+        player.exit(ctx.program_id)?;
+        offered_players.push(player_info.key());
+    }
+    
+    let trade_proposal = &mut ctx.accounts.trade_proposal;
+    let clock = Clock::get()?;
+    
+    trade_proposal.proposer = proposer.key();
+    trade_proposal.counterparty = ctx.accounts.counterparty.key();
+    trade_proposal.trade_id = trade_id;
+    trade_proposal.offered_players = offered_players;
+    trade_proposal.requested_players = requested_players;
+    trade_proposal.proposer_lamports = proposer_lamports;
+    trade_proposal.counterparty_lamports = counterparty_lamports;
+    trade_proposal.sweetener_mint = sweetener_mint;
+    trade_proposal.proposer_tokens = proposer_tokens;
+    trade_proposal.counterparty_tokens = counterparty_tokens;
+    trade_proposal.created_at = clock.unix_timestamp;
+    
+    msg!(
+        "Trade proposed: {} players for {} players",
+        trade_proposal.offered_players.len(),
+        trade_proposal.requested_players.len()
+    );
+    
+    Ok(())
+}
+
+// Accept a trade: both sides' players, lamports and tokens change hands in one go,
+// and any rostered player is released from their team
+pub fn accept_trade<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptTrade<'info>>) -> Result<()> {
+    let trade_proposal = &ctx.accounts.trade_proposal;
+    let offered_count = trade_proposal.offered_players.len();
+    let requested_count = trade_proposal.requested_players.len();
+    
+    let player_group_count = (offered_count + requested_count) * ACCEPT_ACCOUNTS_PER_PLAYER;
+    require!(ctx.remaining_accounts.len() >= player_group_count, ErrorCode::InvalidTradeAccounts);
+    
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let proposer = ctx.accounts.proposer.to_account_info();
+    let counterparty = ctx.accounts.counterparty.to_account_info();
+    let trade_info = trade_proposal.to_account_info();
+    let token_program = &ctx.accounts.token_program;
+    
+    let trade_id_bytes = trade_proposal.trade_id.to_le_bytes();
+    let seeds = &[
+        b"trade".as_ref(),
+        trade_proposal.proposer.as_ref(),
+        trade_id_bytes.as_ref(),
+        &[ctx.bumps.trade_proposal],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let (player_accounts, mut roster_accounts) = ctx.remaining_accounts.split_at(player_group_count);
+    let (offered_accounts, requested_accounts) = player_accounts.split_at(offered_count * ACCEPT_ACCOUNTS_PER_PLAYER);
+    
+    // Proposer's players come out of escrow to the counterparty
+    for (accounts, expected) in offered_accounts.chunks(ACCEPT_ACCOUNTS_PER_PLAYER).zip(&trade_proposal.offered_players) {
+        let mut player = Account::<PlayerAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(player.key(), *expected, ErrorCode::InvalidTradeAccounts);
+        require_keys_eq!(player.owner, proposer.key(), ErrorCode::UnauthorizedAccess);
+        require!(player.user.is_none(), ErrorCode::PlayerRented);
+        
+        let escrow = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let (escrow_address, _) = Pubkey::find_program_address(
+            &[b"trade_escrow", trade_info.key.as_ref(), player.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(escrow.key(), escrow_address, ErrorCode::InvalidTradeAccounts);
+        
+        let destination = Account::<TokenAccount>::try_from(&accounts[2])?;
+        require!(
+            destination.mint == player.mint && destination.owner == counterparty.key(),
+            ErrorCode::InvalidTradeAccounts
+        );
+        
+        release_escrow(&trade_info, signer, &escrow, &accounts[2], &proposer, token_program)?;
+        release_traded_player(&mut player, &accounts[3], &accounts[4], &proposer, &mut roster_accounts, now)?;
+        
+        player.owner = counterparty.key();
+        player.escrowed = false;
+        player.exit(ctx.program_id)?;
+    }
+    
+    // Counterparty's players move straight from their wallet to the proposer
+    for (accounts, expected) in requested_accounts.chunks(ACCEPT_ACCOUNTS_PER_PLAYER).zip(&trade_proposal.requested_players) {
+        let mut player = Account::<PlayerAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(player.key(), *expected, ErrorCode::InvalidTradeAccounts);
+        require_keys_eq!(player.owner, counterparty.key(), ErrorCode::UnauthorizedAccess);
+        require!(player.user.is_none(), ErrorCode::PlayerRented);
+        require!(!player.escrowed, ErrorCode::PlayerEscrowed);
+        
+        let destination = Account::<TokenAccount>::try_from(&accounts[2])?;
+        require!(
+            destination.mint == player.mint && destination.owner == proposer.key(),
+            ErrorCode::InvalidTradeAccounts
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: accounts[1].clone(),
+                    to: accounts[2].clone(),
+                    authority: counterparty.clone(),
+                },
+            ),
+            1,
+        )?;
+        release_traded_player(&mut player, &accounts[3], &accounts[4], &counterparty, &mut roster_accounts, now)?;
+        
+        player.owner = proposer.key();
+        player.exit(ctx.program_id)?;
+    }
+    require!(roster_accounts.is_empty(), ErrorCode::InvalidRosterAccounts);
+    
+    // Lamport sweeteners
+    move_lamports(&trade_info, &counterparty, trade_proposal.proposer_lamports)?;
+    pay_from_wallet(
+        &ctx.accounts.system_program.to_account_info(),
+        &counterparty,
+        &proposer,
+        trade_proposal.counterparty_lamports,
+    )?;
+    
+    // Token sweeteners
+    if let Some(mint) = trade_proposal.sweetener_mint {
+        if trade_proposal.proposer_tokens > 0 {
+            let (Some(escrow), Some(destination)) = (
+                ctx.accounts.sweetener_escrow.as_ref(),
+                ctx.accounts.counterparty_sweetener_account.as_ref(),
+            ) else {
+                return Err(ErrorCode::InvalidTradeAccounts.into());
+            };
+            require_keys_eq!(destination.mint, mint, ErrorCode::InvalidTradeAccounts);
+            
+            release_sweetener_escrow(&trade_info, signer, escrow, &destination.to_account_info(), &proposer, mint, token_program)?;
+        }
+        
+        if trade_proposal.counterparty_tokens > 0 {
+            let (Some(source), Some(destination)) = (
+                ctx.accounts.counterparty_sweetener_account.as_ref(),
+                ctx.accounts.proposer_sweetener_account.as_ref(),
+            ) else {
+                return Err(ErrorCode::InvalidTradeAccounts.into());
+            };
+            require!(source.mint == mint && destination.mint == mint, ErrorCode::InvalidTradeAccounts);
+            
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: source.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: counterparty.clone(),
+                    },
+                ),
+                trade_proposal.counterparty_tokens,
+            )?;
+        }
+    }
+    
+    msg!("Trade {} completed", trade_proposal.trade_id);
+    
+    Ok(())
+}
+
+// Call off a trade, returning everything the proposer escrowed
+pub fn cancel_trade<'info>(ctx: Context<'_, '_, 'info, 'info, CancelTrade<'info>>) -> Result<()> {
+    let trade_proposal = &ctx.accounts.trade_proposal;
+    
+    require!(
+        ctx.remaining_accounts.len() == trade_proposal.offered_players.len() * CANCEL_ACCOUNTS_PER_PLAYER,
+        ErrorCode::InvalidTradeAccounts
+    );
+    
+    let proposer = ctx.accounts.proposer.to_account_info();
+    let trade_info = trade_proposal.to_account_info();
+    let token_program = &ctx.accounts.token_program;
+    
+    let trade_id_bytes = trade_proposal.trade_id.to_le_bytes();
+    let seeds = &[
+        b"trade".as_ref(),
+        trade_proposal.proposer.as_ref(),
+        trade_id_bytes.as_ref(),
+        &[ctx.bumps.trade_proposal],
+    ];
+    let signer = &[&seeds[..]];
+    
+    for (accounts, expected) in ctx.remaining_accounts.chunks(CANCEL_ACCOUNTS_PER_PLAYER).zip(&trade_proposal.offered_players) {
+        let mut player = Account::<PlayerAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(player.key(), *expected, ErrorCode::InvalidTradeAccounts);
+        
+        let escrow = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let (escrow_address, _) = Pubkey::find_program_address(
+            &[b"trade_escrow", trade_info.key.as_ref(), player.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(escrow.key(), escrow_address, ErrorCode::InvalidTradeAccounts);
+        
+        let destination = Account::<TokenAccount>::try_from(&accounts[2])?;
+        require!(
+            destination.mint == escrow.mint && destination.owner == proposer.key(),
+            ErrorCode::InvalidTradeAccounts
+        );
+        
+        release_escrow(&trade_info, signer, &escrow, &accounts[2], &proposer, token_program)?;
+        
+        player.escrowed = false;
+        player.exit(ctx.program_id)?;
+    }
+    
+    if let Some(mint) = trade_proposal.sweetener_mint {
+        if trade_proposal.proposer_tokens > 0 {
+            let (Some(escrow), Some(destination)) = (
+                ctx.accounts.sweetener_escrow.as_ref(),
+                ctx.accounts.proposer_sweetener_account.as_ref(),
+            ) else {
+                return Err(ErrorCode::InvalidTradeAccounts.into());
+            };
+            require_keys_eq!(destination.mint, mint, ErrorCode::InvalidTradeAccounts);
+            
+            release_sweetener_escrow(&trade_info, signer, escrow, &destination.to_account_info(), &proposer, mint, token_program)?;
+        }
+    }
+    
+    msg!("Trade {} canceled", trade_proposal.trade_id);
+    
+    Ok(())
+}
+
+// Create a token escrow owned by the trade at [b"trade_escrow", trade, mint]
+fn create_trade_escrow<'info>(
+    trade_info: &AccountInfo<'info>,
+    escrow_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (escrow_address, bump) = Pubkey::find_program_address(
+        &[b"trade_escrow", trade_info.key.as_ref(), mint_info.key.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(escrow_info.key(), escrow_address, ErrorCode::InvalidTradeAccounts);
+    
+    let space = TokenAccount::LEN;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::CreateAccount {
+                from: payer.clone(),
+                to: escrow_info.clone(),
+            },
+            &[&[b"trade_escrow", trade_info.key.as_ref(), mint_info.key.as_ref(), &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &token::ID,
+    )?;
+    
+    token::initialize_account3(CpiContext::new(
+        token_program.clone(),
+        InitializeAccount3 {
+            account: escrow_info.clone(),
+            mint: mint_info.clone(),
+            authority: trade_info.clone(),
+        },
+    ))
+}
+
+// Empty the sweetener escrow into the destination and close it
+fn release_sweetener_escrow<'info>(
+    trade_info: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    escrow: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    mint: Pubkey,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let (escrow_address, _) = Pubkey::find_program_address(
+        &[b"trade_escrow", trade_info.key.as_ref(), mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(escrow.key(), escrow_address, ErrorCode::InvalidTradeAccounts);
+    
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow.to_account_info(),
+                to: destination.clone(),
+                authority: trade_info.clone(),
+            },
+            signer,
+        ),
+        escrow.amount,
+    )?;
+    
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: rent_receiver.clone(),
+            authority: trade_info.clone(),
+        },
+        signer,
+    ))
+}
+
+// Take a traded player off their team, if rostered, and tear up the contract.
+// Only the owner's own team can be released this way, so loaned-out players cannot be traded.
+// The team's remaining roster is taken from the front of `roster_accounts` to refresh its statistics
+fn release_traded_player<'info>(
+    player: &mut Account<'info, PlayerAccount>,
+    team_info: &'info AccountInfo<'info>,
+    contract_info: &'info AccountInfo<'info>,
+    team_owner: &AccountInfo<'info>,
+    roster_accounts: &mut &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<()> {
+    let Some(team_key) = player.team else {
+        return Ok(());
+    };
+    
+    let mut team_account = Account::<TeamAccount>::try_from(team_info)?;
+    require_keys_eq!(team_account.key(), team_key, ErrorCode::PlayerNotOnTeam);
+    require_keys_eq!(team_account.owner, team_owner.key(), ErrorCode::UnauthorizedAccess);
+    
+    let player_contract = Account::<PlayerContract>::try_from(contract_info)?;
+    require!(
+        player_contract.team == team_key && player_contract.player == player.key(),
+        ErrorCode::InvalidContractTerms
+    );
+    
+    // Wages already earned must be paid before a release
+    require!(player_contract.periods_due(now) == 0, ErrorCode::SalaryOutstanding);
+    
+    remove_from_roster(&mut team_account, &player.mint)?;
+    team_account.last_updated = now;
+    
+    require!(roster_accounts.len() >= team_account.roster.len(), ErrorCode::InvalidRosterAccounts);
+    let (team_roster, rest) = roster_accounts.split_at(team_account.roster.len());
+    refresh_roster_statistics(&mut team_account, team_key, team_roster, None)?;
+    *roster_accounts = rest;
+    
+    team_account.exit(&crate::ID)?;
+    player_contract.close(team_owner.clone())?;
+    
+    player.team = None;
+    
+    Ok(())
+}
@@ -1,5 +1,17 @@
 use anchor_lang::prelude::*;
 use std::str::FromStr;
+use crate::errors::ErrorCode;
+
+// Length of a competitive season
+pub const SEASON_DURATION: i64 = 90 * 86400;
+
+// A competitive year is four seasons (one split per quarter)
+pub const SEASONS_PER_YEAR: i64 = 4;
+
+// Season index for a timestamp (seasons are fixed windows since the unix epoch)
+pub fn current_season(timestamp: i64) -> u32 {
+    (timestamp / SEASON_DURATION) as u32
+}
 
 // Safely update a stat with bounds checking
 pub fn safe_update_stat(stat: &mut u8, change: i8) {
@@ -36,6 +48,20 @@ pub fn is_admin(_key: &Pubkey) -> bool {
     // *key == admin_key
 }
 
+// Move lamports out of a program-owned escrow account
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    **from.try_borrow_mut_lamports()? = from.lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::EscrowUnderfunded)?;
+    **to.try_borrow_mut_lamports()? += amount;
+    
+    Ok(())
+}
+
 // PDA helpers for finding various account addresses
 pub mod pda {
     use super::*;
@@ -55,6 +81,10 @@ pub mod pda {
         Pubkey::find_program_address(&[b"creator", authority.as_ref()], program_id)
     }
     
+    pub fn find_treasury_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"treasury"], program_id)
+    }
+    
     pub fn find_tournament_address(authority: &Pubkey, name: &str, program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(
             &[b"tournament", authority.as_ref(), name.as_bytes()],
@@ -0,0 +1,218 @@
+// Shared setup for the bankrun suites: transaction helpers, players, teams and account readers.
+// Runs against tests/fixtures/esports_manager_contract.so: rebuild with `anchor build` and copy
+// target/deploy/esports_manager_contract.so over the fixture before running.
+import { ComputeBudgetProgram, Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, TransactionInstruction } from '@solana/web3.js';
+import {
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  getAssociatedTokenAddressSync,
+} from '@solana/spl-token';
+import { Clock, ProgramTestContext, start } from 'solana-bankrun';
+import { createHash } from 'crypto';
+import { PROGRAM_ID, absent, anchorInstruction, borsh, findPDA, fundAccount, readonly, strToBuffer, writable } from './helper';
+
+export const SOL = 1_000_000_000n;
+
+// Position enum indices
+export const TOP = 0;
+export const JUNGLE = 1;
+export const MID = 2;
+
+export interface Player {
+  mint: PublicKey;
+  player: PublicKey;
+  tokenAccount: PublicKey;
+}
+
+// Discriminator of an Anchor account type
+export function accountDiscriminator(name: string): Buffer {
+  return createHash('sha256').update(`account:${name}`).digest().subarray(0, 8);
+}
+
+export class Harness {
+  private failedAttempts = 0;
+
+  private constructor(readonly context: ProgramTestContext) {}
+
+  static async start(): Promise<Harness> {
+    return new Harness(await start([{ name: 'esports_manager_contract', programId: PROGRAM_ID }], []));
+  }
+
+  async send(instructions: TransactionInstruction[], signers: Keypair[] = []): Promise<void> {
+    const tx = new Transaction().add(...instructions);
+    tx.recentBlockhash = this.context.lastBlockhash;
+    tx.feePayer = this.context.payer.publicKey;
+    tx.sign(this.context.payer, ...signers);
+    await this.context.banksClient.processTransaction(tx);
+  }
+
+  // Failed transactions stay in the status cache, so each attempt gets its own compute limit
+  // to keep a later, identical successful transaction from being rejected as already processed
+  async expectError(instructions: TransactionInstruction[], signers: Keypair[], code: string): Promise<void> {
+    const tx = new Transaction().add(
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 + this.failedAttempts++ }),
+      ...instructions,
+    );
+    tx.recentBlockhash = this.context.lastBlockhash;
+    tx.feePayer = this.context.payer.publicKey;
+    tx.sign(this.context.payer, ...signers);
+    const { result, meta } = await this.context.banksClient.tryProcessTransaction(tx);
+    expect(result).not.toBeNull();
+    expect(meta?.logMessages.join('\n')).toContain(`Error Code: ${code}`);
+  }
+
+  async wallet(): Promise<Keypair> {
+    const keypair = Keypair.generate();
+    await fundAccount(this.context, keypair.publicKey);
+    return keypair;
+  }
+
+  async balance(address: PublicKey): Promise<bigint> {
+    return this.context.banksClient.getBalance(address);
+  }
+
+  async isClosed(address: PublicKey): Promise<boolean> {
+    return (await this.context.banksClient.getAccount(address)) === null;
+  }
+
+  async data(address: PublicKey): Promise<Buffer> {
+    const account = await this.context.banksClient.getAccount(address);
+    return Buffer.from(account!.data);
+  }
+
+  async advanceClock(seconds: bigint): Promise<void> {
+    const clock = await this.context.banksClient.getClock();
+    this.context.setClock(new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp + seconds,
+    ));
+  }
+
+  async advanceSlots(slots: bigint): Promise<void> {
+    const clock = await this.context.banksClient.getClock();
+    this.context.warpToSlot(clock.slot + slots);
+  }
+
+  // Write the program config directly; initialize_config needs the program's upgrade authority
+  async setConfig(authority: PublicKey): Promise<PublicKey> {
+    const [config] = findPDA([strToBuffer('config')], PROGRAM_ID);
+    const data = Buffer.concat([accountDiscriminator('ProgramConfig'), authority.toBuffer(), borsh.i64(0)]);
+    const rent = await this.context.banksClient.getRent();
+    this.context.setAccount(config, {
+      executable: false,
+      owner: PROGRAM_ID,
+      lamports: Number(rent.minimumBalance(BigInt(data.length))),
+      data,
+    });
+    return config;
+  }
+
+  // Create an empty 0-decimal mint with `authority` as mint authority
+  async mint(authority: PublicKey): Promise<PublicKey> {
+    const mint = Keypair.generate();
+    const rent = await this.context.banksClient.getRent();
+    await this.send([
+      SystemProgram.createAccount({
+        fromPubkey: this.context.payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+        space: MINT_SIZE,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(mint.publicKey, 0, authority, null),
+    ], [mint]);
+    return mint.publicKey;
+  }
+
+  // Mint a one-of-one NFT to `owner` and create its player account
+  async mintPlayer(owner: Keypair, name: string, position = MID): Promise<Player> {
+    const mint = await this.mint(owner.publicKey);
+    const tokenAccount = getAssociatedTokenAddressSync(mint, owner.publicKey);
+    await this.send([
+      createAssociatedTokenAccountInstruction(this.context.payer.publicKey, tokenAccount, owner.publicKey, mint),
+      createMintToInstruction(mint, tokenAccount, owner.publicKey, 1),
+    ], [owner]);
+
+    const [player] = findPDA([strToBuffer('player'), mint.toBuffer()], PROGRAM_ID);
+    await this.send([anchorInstruction('initialize_player', [
+      writable(owner.publicKey, true),
+      readonly(mint),
+      writable(player),
+      absent(),
+      absent(),
+      readonly(SystemProgram.programId),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SYSVAR_RENT_PUBKEY),
+    ], borsh.string(name), borsh.u8(position), borsh.vec([]), borsh.string(`https://5vs5.gg/player/${mint.toBase58()}.json`))], [owner]);
+
+    return { mint, player, tokenAccount };
+  }
+
+  async createTeam(owner: Keypair, name: string): Promise<PublicKey> {
+    const [team] = findPDA([strToBuffer('team'), owner.publicKey.toBuffer(), strToBuffer(name)], PROGRAM_ID);
+    await this.send([anchorInstruction('create_team', [
+      writable(owner.publicKey, true),
+      writable(team),
+      readonly(SystemProgram.programId),
+      readonly(SYSVAR_RENT_PUBKEY),
+    ], borsh.string(name), borsh.string(''))], [owner]);
+    return team;
+  }
+
+  // Sign `p` to the team; `roster` are the players already on it
+  async addPlayerToTeam(
+    owner: Keypair,
+    team: PublicKey,
+    p: Player,
+    position: number,
+    salary: bigint,
+    periods: number,
+    roster: Player[] = [],
+  ): Promise<PublicKey> {
+    const contract = contractAddress(team, p.player);
+    await this.send([anchorInstruction('add_player_to_team', [
+      writable(owner.publicKey, true),
+      writable(team),
+      writable(p.player),
+      readonly(p.mint),
+      writable(contract),
+      readonly(SystemProgram.programId),
+      ...roster.map(r => readonly(r.player)),
+    ], p.mint.toBuffer(), borsh.u8(position), borsh.u64(salary), borsh.u16(periods))], [owner]);
+    return contract;
+  }
+
+  // Player mints on a team's roster, in roster order
+  async roster(team: PublicKey): Promise<PublicKey[]> {
+    const data = await this.data(team);
+    let offset = 8 + 32;
+    offset += 4 + data.readUInt32LE(offset); // name
+    offset += data[offset] === 1 ? 33 : 1; // collection_mint
+    offset += 4 + data.readUInt32LE(offset); // logo_uri
+    offset += 8 + 8; // created_at, last_updated
+    const count = data.readUInt32LE(offset);
+    offset += 4;
+    return Array.from({ length: count }, (_, i) => new PublicKey(data.subarray(offset + i * 41, offset + i * 41 + 32)));
+  }
+
+  // The team a player currently plays for, if any
+  async playerTeam(player: PublicKey): Promise<PublicKey | null> {
+    const data = await this.data(player);
+    const offset = 8 + 32 + 32 + 4 + data.readUInt32LE(72) + 1 + 8 + 8;
+    return data[offset] === 1 ? new PublicKey(data.subarray(offset + 1, offset + 33)) : null;
+  }
+}
+
+export function contractAddress(team: PublicKey, player: PublicKey): PublicKey {
+  return findPDA([strToBuffer('contract'), team.toBuffer(), player.toBuffer()], PROGRAM_ID)[0];
+}
+
+export function treasuryAddress(team: PublicKey): PublicKey {
+  return findPDA([strToBuffer('team_treasury'), team.toBuffer()], PROGRAM_ID)[0];
+}
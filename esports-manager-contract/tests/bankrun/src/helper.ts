@@ -1,11 +1,11 @@
 import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, TransactionInstruction } from '@solana/web3.js';
 import { BanksClient, ProgramTestContext } from 'solana-bankrun';
-import * as anchor from '@coral-xyz/anchor';
+import { createHash } from 'crypto';
 import fs from 'fs';
 import path from 'path';
 
-// Replace with your program ID or use a dynamic one for testing
-export const PROGRAM_ID = new PublicKey('57wMKYdCPiA8tn28t2ucZkxEz9Lvd9eMLDLXf5kJzR1h');
+// Program ID from declare_id! in the program's lib.rs
+export const PROGRAM_ID = new PublicKey('2KBakNVa6xLxp6uQsgHhikrknw1pkjkS2f6ZGKtV5BzZ');
 
 // Token program ID
 export const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
@@ -109,7 +109,7 @@ export function createInstructionData(discriminator: number, ...args: any[]): Bu
 
 // Generate the correct Anchor discriminator for an instruction
 export function getAnchorDiscriminator(name: string): Buffer {
-  return Buffer.from(anchor.utils.sha256.hash(`instruction:${name}`).slice(0, 8));
+  return createHash('sha256').update(`global:${name}`).digest().subarray(0, 8);
 }
 
 // Common accounts needed for most instructions
@@ -119,4 +119,53 @@ export function getCommonAccounts() {
     tokenProgram: TOKEN_PROGRAM_ID,
     rent: SYSVAR_RENT_PUBKEY
   };
-}
\ No newline at end of file
+}
+// Borsh encoders for Anchor instruction arguments
+export const borsh = {
+  u8: (value: number): Buffer => Buffer.from([value]),
+  u16: (value: number): Buffer => {
+    const buf = Buffer.alloc(2);
+    buf.writeUInt16LE(value, 0);
+    return buf;
+  },
+  u64: (value: number | bigint): Buffer => {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value), 0);
+    return buf;
+  },
+  i64: (value: number | bigint): Buffer => {
+    const buf = Buffer.alloc(8);
+    buf.writeBigInt64LE(BigInt(value), 0);
+    return buf;
+  },
+  string: (value: string): Buffer => {
+    const bytes = Buffer.from(value);
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(bytes.length, 0);
+    return Buffer.concat([len, bytes]);
+  },
+  vec: (items: Buffer[]): Buffer => {
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(items.length, 0);
+    return Buffer.concat([len, ...items]);
+  },
+  none: (): Buffer => Buffer.from([0]),
+};
+
+// Build an Anchor instruction from its method name, accounts (in context struct order) and encoded args
+export function anchorInstruction(
+  name: string,
+  keys: { pubkey: PublicKey, isSigner: boolean, isWritable: boolean }[],
+  ...args: Buffer[]
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys,
+    data: Buffer.concat([getAnchorDiscriminator(name), ...args]),
+  });
+}
+
+// Account meta shorthands; an absent Option<Account> is passed as the program id
+export const writable = (pubkey: PublicKey, isSigner = false) => ({ pubkey, isSigner, isWritable: true });
+export const readonly = (pubkey: PublicKey, isSigner = false) => ({ pubkey, isSigner, isWritable: false });
+export const absent = () => readonly(PROGRAM_ID);
@@ -0,0 +1,128 @@
+// Payroll, arrears and contract release paths of team contracts.
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { anchorInstruction, borsh, readonly, writable } from '../src/helper';
+import { Harness, MID, Player, SOL, TOP, treasuryAddress } from '../src/harness';
+
+const SALARY = SOL;
+const PAYROLL_PERIOD = 7n * 86400n;
+
+describe('Contracts and payroll', () => {
+  let h: Harness;
+
+  beforeEach(async () => {
+    h = await Harness.start();
+  });
+
+  function fundIx(funder: Keypair, team: PublicKey, amount: bigint) {
+    return anchorInstruction('fund_team_treasury', [
+      writable(funder.publicKey, true),
+      readonly(team),
+      writable(treasuryAddress(team)),
+      readonly(SystemProgram.programId),
+    ], borsh.u64(amount));
+  }
+
+  function runPayrollIx(team: PublicKey, p: Player, contract: PublicKey, playerOwner: PublicKey) {
+    return anchorInstruction('run_payroll', [
+      readonly(h.context.payer.publicKey, true),
+      writable(contract),
+      writable(p.player),
+      writable(treasuryAddress(team)),
+      writable(playerOwner),
+      readonly(SystemProgram.programId),
+    ]);
+  }
+
+  function terminateIx(owner: Keypair, team: PublicKey, p: Player, contract: PublicKey, roster: Player[] = []) {
+    return anchorInstruction('terminate_unpaid_contract', [
+      readonly(owner.publicKey, true),
+      writable(contract),
+      writable(team),
+      writable(p.player),
+      writable(owner.publicKey),
+      ...roster.map(r => readonly(r.player)),
+    ]);
+  }
+
+  function expireIx(teamOwner: PublicKey, team: PublicKey, p: Player, contract: PublicKey, roster: Player[] = []) {
+    return anchorInstruction('expire_contract', [
+      readonly(h.context.payer.publicKey, true),
+      writable(contract),
+      writable(team),
+      writable(p.player),
+      writable(teamOwner),
+      ...roster.map(r => readonly(r.player)),
+    ]);
+  }
+
+  test('payroll pays each due period and the player walks out once two periods are unpaid', async () => {
+    const owner = await h.wallet();
+    const p = await h.mintPlayer(owner, 'Ace');
+    const team = await h.createTeam(owner, 'Payroll');
+    const contract = await h.addPlayerToTeam(owner, team, p, MID, SALARY, 4);
+    await h.send([fundIx(owner, team, SALARY)], [owner]);
+
+    await h.expectError([runPayrollIx(team, p, contract, owner.publicKey)], [], 'NoSalaryDue');
+
+    await h.advanceClock(PAYROLL_PERIOD);
+    const before = await h.balance(owner.publicKey);
+    await h.send([runPayrollIx(team, p, contract, owner.publicKey)]);
+    expect(await h.balance(owner.publicKey)).toBe(before + SALARY);
+
+    // Paid up, so the owner can't walk away
+    await h.expectError([terminateIx(owner, team, p, contract)], [owner], 'ContractInGoodStanding');
+
+    // One missed period is still good standing
+    await h.advanceClock(PAYROLL_PERIOD);
+    await h.expectError([runPayrollIx(team, p, contract, owner.publicKey)], [], 'InsufficientTeamFunds');
+    await h.expectError([terminateIx(owner, team, p, contract)], [owner], 'ContractInGoodStanding');
+
+    // Two missed periods end it
+    await h.advanceClock(PAYROLL_PERIOD);
+    await h.send([terminateIx(owner, team, p, contract)], [owner]);
+
+    expect(await h.isClosed(contract)).toBe(true);
+    expect(await h.roster(team)).toEqual([]);
+    expect(await h.playerTeam(p.player)).toBeNull();
+  });
+
+  test('only the player owner can terminate an unpaid contract', async () => {
+    const owner = await h.wallet();
+    const stranger = await h.wallet();
+    const p = await h.mintPlayer(owner, 'Ace');
+    const team = await h.createTeam(owner, 'Payroll');
+    const contract = await h.addPlayerToTeam(owner, team, p, MID, SALARY, 4);
+
+    await h.advanceClock(2n * PAYROLL_PERIOD);
+    await h.expectError([anchorInstruction('terminate_unpaid_contract', [
+      readonly(stranger.publicKey, true),
+      writable(contract),
+      writable(team),
+      writable(p.player),
+      writable(owner.publicKey),
+    ])], [stranger], 'UnauthorizedAccess');
+  });
+
+  test('an expired contract releases the player only once every period is paid', async () => {
+    const owner = await h.wallet();
+    const leaving = await h.mintPlayer(owner, 'Ace');
+    const staying = await h.mintPlayer(owner, 'Bolt', TOP);
+    const team = await h.createTeam(owner, 'Payroll');
+    const contract = await h.addPlayerToTeam(owner, team, leaving, MID, SALARY, 1);
+    await h.addPlayerToTeam(owner, team, staying, TOP, SALARY, 4, [leaving]);
+    await h.send([fundIx(owner, team, SALARY)], [owner]);
+
+    await h.expectError([expireIx(owner.publicKey, team, leaving, contract, [staying])], [], 'ContractActive');
+
+    await h.advanceClock(PAYROLL_PERIOD);
+    await h.expectError([expireIx(owner.publicKey, team, leaving, contract, [staying])], [], 'SalaryOutstanding');
+
+    await h.send([runPayrollIx(team, leaving, contract, owner.publicKey)]);
+    await h.send([expireIx(owner.publicKey, team, leaving, contract, [staying])]);
+
+    expect(await h.isClosed(contract)).toBe(true);
+    expect((await h.roster(team)).map(m => m.toBase58())).toEqual([staying.mint.toBase58()]);
+    expect(await h.playerTeam(leaving.player)).toBeNull();
+    expect((await h.playerTeam(staying.player))?.toBase58()).toBe(team.toBase58());
+  });
+});
@@ -0,0 +1,624 @@
+// Escrow, payout and royalty paths of the marketplace, auctions and trades.
+// Runs against tests/fixtures/esports_manager_contract.so: rebuild with `anchor build` and copy
+// target/deploy/esports_manager_contract.so over the fixture before running.
+import { ComputeBudgetProgram, Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, TransactionInstruction } from '@solana/web3.js';
+import {
+  MINT_SIZE,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  getAssociatedTokenAddressSync,
+} from '@solana/spl-token';
+import { Clock, ProgramTestContext, start } from 'solana-bankrun';
+import { PROGRAM_ID, absent, anchorInstruction, borsh, findPDA, fundAccount, readonly, strToBuffer, writable } from '../src/helper';
+
+const SOL = 1_000_000_000n;
+const PROTOCOL_FEE_BPS = 250n;
+const CREATOR_FEE_BPS = 500n;
+const AUCTION_DURATION = 3600n;
+
+const ENGLISH = 0;
+const MID = 2;
+
+interface Player {
+  mint: PublicKey;
+  player: PublicKey;
+  tokenAccount: PublicKey;
+}
+
+describe('Escrow and payouts', () => {
+  let context: ProgramTestContext;
+  let treasury: PublicKey;
+  let failedAttempts = 0;
+
+  beforeEach(async () => {
+    context = await start([{ name: 'esports_manager_contract', programId: PROGRAM_ID }], []);
+    [treasury] = findPDA([strToBuffer('treasury')], PROGRAM_ID);
+  });
+
+  // Helpers
+
+  async function send(instructions: TransactionInstruction[], signers: Keypair[] = []): Promise<void> {
+    const tx = new Transaction().add(...instructions);
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    await context.banksClient.processTransaction(tx);
+  }
+
+  // Failed transactions stay in the status cache, so each attempt gets its own compute limit
+  // to keep a later, identical successful transaction from being rejected as already processed
+  async function expectError(instructions: TransactionInstruction[], signers: Keypair[], code: string): Promise<void> {
+    const tx = new Transaction().add(
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 + failedAttempts++ }),
+      ...instructions,
+    );
+    tx.recentBlockhash = context.lastBlockhash;
+    tx.feePayer = context.payer.publicKey;
+    tx.sign(context.payer, ...signers);
+    const { result, meta } = await context.banksClient.tryProcessTransaction(tx);
+    expect(result).not.toBeNull();
+    expect(meta?.logMessages.join('\n')).toContain(`Error Code: ${code}`);
+  }
+
+  async function wallet(): Promise<Keypair> {
+    const keypair = Keypair.generate();
+    await fundAccount(context, keypair.publicKey);
+    return keypair;
+  }
+
+  async function balance(address: PublicKey): Promise<bigint> {
+    return context.banksClient.getBalance(address);
+  }
+
+  async function isClosed(address: PublicKey): Promise<boolean> {
+    return (await context.banksClient.getAccount(address)) === null;
+  }
+
+  async function tokenAmount(address: PublicKey): Promise<bigint> {
+    const account = await context.banksClient.getAccount(address);
+    return account ? Buffer.from(account.data).readBigUInt64LE(64) : 0n;
+  }
+
+  async function playerOwner(player: PublicKey): Promise<PublicKey> {
+    const account = await context.banksClient.getAccount(player);
+    return new PublicKey(Buffer.from(account!.data).subarray(8, 40));
+  }
+
+  async function advanceClock(seconds: bigint): Promise<void> {
+    const clock = await context.banksClient.getClock();
+    context.setClock(new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp + seconds,
+    ));
+  }
+
+  function split(price: bigint, royaltyBps = 0n) {
+    const protocolFee = price * PROTOCOL_FEE_BPS / 10000n;
+    const royalty = price * royaltyBps / 10000n;
+    return { sellerProceeds: price - protocolFee - royalty, protocolFee, royalty };
+  }
+
+  async function tokenAccount(owner: PublicKey, mint: PublicKey): Promise<PublicKey> {
+    const address = getAssociatedTokenAddressSync(mint, owner);
+    await send([createAssociatedTokenAccountInstruction(context.payer.publicKey, address, owner, mint)]);
+    return address;
+  }
+
+  // Mint a one-of-one NFT to `owner` and create its player account, through the creator when given
+  async function mintPlayer(owner: Keypair, name: string, creator?: Keypair): Promise<Player> {
+    const mint = Keypair.generate();
+    const rent = await context.banksClient.getRent();
+    await send([
+      SystemProgram.createAccount({
+        fromPubkey: context.payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+        space: MINT_SIZE,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(mint.publicKey, 0, owner.publicKey, null),
+    ], [mint]);
+
+    const ownerTokenAccount = await tokenAccount(owner.publicKey, mint.publicKey);
+    await send([createMintToInstruction(mint.publicKey, ownerTokenAccount, owner.publicKey, 1)], [owner]);
+
+    const [player] = findPDA([strToBuffer('player'), mint.publicKey.toBuffer()], PROGRAM_ID);
+    const uri = `https://5vs5.gg/player/${mint.publicKey.toBase58()}.json`;
+
+    if (creator) {
+      const [creatorAccount] = findPDA([strToBuffer('creator'), creator.publicKey.toBuffer()], PROGRAM_ID);
+      await send([anchorInstruction('create_exclusive_athlete', [
+        writable(creator.publicKey, true),
+        writable(creatorAccount),
+        readonly(mint.publicKey),
+        writable(player),
+        writable(owner.publicKey, true),
+        readonly(SystemProgram.programId),
+        readonly(TOKEN_PROGRAM_ID),
+        readonly(SYSVAR_RENT_PUBKEY),
+      ], borsh.string(name), borsh.u8(MID), borsh.string(uri), borsh.none(), borsh.none(), borsh.none())], [creator, owner]);
+    } else {
+      await send([anchorInstruction('initialize_player', [
+        writable(owner.publicKey, true),
+        readonly(mint.publicKey),
+        writable(player),
+        absent(),
+        absent(),
+        readonly(SystemProgram.programId),
+        readonly(TOKEN_PROGRAM_ID),
+        readonly(SYSVAR_RENT_PUBKEY),
+      ], borsh.string(name), borsh.u8(MID), borsh.vec([]), borsh.string(uri))], [owner]);
+    }
+
+    return { mint: mint.publicKey, player, tokenAccount: ownerTokenAccount };
+  }
+
+  // Register and verify a creator charging CREATOR_FEE_BPS
+  async function verifiedCreator(): Promise<{ authority: Keypair, account: PublicKey }> {
+    const authority = await wallet();
+    const [account] = findPDA([strToBuffer('creator'), authority.publicKey.toBuffer()], PROGRAM_ID);
+    await send([
+      anchorInstruction('register_creator', [
+        writable(authority.publicKey, true),
+        writable(account),
+        readonly(SystemProgram.programId),
+        readonly(SYSVAR_RENT_PUBKEY),
+      ], borsh.string('Studio'), borsh.u16(Number(CREATOR_FEE_BPS))),
+      anchorInstruction('verify_creator', [writable(authority.publicKey, true), writable(account)]),
+    ], [authority]);
+    return { authority, account };
+  }
+
+  // Creator accounts for a sale: both absent for a regular player
+  function creatorAccounts(creator?: { authority: Keypair, account: PublicKey }) {
+    return creator
+      ? [readonly(creator.account), writable(creator.authority.publicKey)]
+      : [absent(), absent()];
+  }
+
+  // Marketplace
+
+  function listingAddresses(player: PublicKey) {
+    const [listing] = findPDA([strToBuffer('listing'), player.toBuffer()], PROGRAM_ID);
+    const [escrow] = findPDA([strToBuffer('escrow'), listing.toBuffer()], PROGRAM_ID);
+    return { listing, escrow };
+  }
+
+  function listIx(seller: Keypair, p: Player, price: bigint) {
+    const { listing, escrow } = listingAddresses(p.player);
+    return anchorInstruction('list_player', [
+      writable(seller.publicKey, true),
+      writable(p.player),
+      readonly(p.mint),
+      writable(p.tokenAccount),
+      writable(listing),
+      writable(escrow),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SystemProgram.programId),
+    ], borsh.u64(price));
+  }
+
+  function delistIx(seller: Keypair, p: Player) {
+    const { listing, escrow } = listingAddresses(p.player);
+    return anchorInstruction('delist_player', [
+      writable(seller.publicKey, true),
+      writable(listing),
+      writable(p.player),
+      writable(escrow),
+      writable(p.tokenAccount),
+      readonly(TOKEN_PROGRAM_ID),
+    ]);
+  }
+
+  function buyIx(buyer: Keypair, seller: PublicKey, p: Player, buyerTokenAccount: PublicKey, creator?: { authority: Keypair, account: PublicKey }) {
+    const { listing, escrow } = listingAddresses(p.player);
+    return anchorInstruction('buy_player', [
+      writable(buyer.publicKey, true),
+      writable(listing),
+      writable(p.player),
+      writable(escrow),
+      writable(buyerTokenAccount),
+      writable(seller),
+      writable(treasury),
+      ...creatorAccounts(creator),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SystemProgram.programId),
+    ]);
+  }
+
+  function offerAddress(player: PublicKey, buyer: PublicKey): PublicKey {
+    return findPDA([strToBuffer('purchase_offer'), player.toBuffer(), buyer.toBuffer()], PROGRAM_ID)[0];
+  }
+
+  function makeOfferIx(buyer: Keypair, p: Player, amount: bigint) {
+    return anchorInstruction('make_purchase_offer', [
+      writable(buyer.publicKey, true),
+      readonly(p.player),
+      writable(offerAddress(p.player, buyer.publicKey)),
+      readonly(SystemProgram.programId),
+    ], borsh.u64(amount));
+  }
+
+  function acceptOfferIx(seller: Keypair, buyer: PublicKey, p: Player, buyerTokenAccount: PublicKey, creator?: { authority: Keypair, account: PublicKey }) {
+    return anchorInstruction('accept_purchase_offer', [
+      writable(seller.publicKey, true),
+      writable(p.player),
+      writable(offerAddress(p.player, buyer)),
+      writable(buyer),
+      writable(p.tokenAccount),
+      writable(buyerTokenAccount),
+      writable(treasury),
+      ...creatorAccounts(creator),
+      readonly(TOKEN_PROGRAM_ID),
+    ]);
+  }
+
+  test('delisting releases the escrowed NFT back to the seller', async () => {
+    const seller = await wallet();
+    const p = await mintPlayer(seller, 'Caps');
+    const { listing, escrow } = listingAddresses(p.player);
+
+    await send([listIx(seller, p, SOL)], [seller]);
+    expect(await tokenAmount(escrow)).toBe(1n);
+    expect(await tokenAmount(p.tokenAccount)).toBe(0n);
+
+    await send([delistIx(seller, p)], [seller]);
+    expect(await tokenAmount(p.tokenAccount)).toBe(1n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(listing)).toBe(true);
+
+    // The escrow lock is lifted, so the player can be listed again
+    await send([listIx(seller, p, 2n * SOL)], [seller]);
+    expect(await tokenAmount(escrow)).toBe(1n);
+  });
+
+  test('buying pays seller, treasury and creator and releases the escrow to the buyer', async () => {
+    const creator = await verifiedCreator();
+    const seller = await wallet();
+    const buyer = await wallet();
+    const p = await mintPlayer(seller, 'Chovy', creator.authority);
+    const buyerTokenAccount = await tokenAccount(buyer.publicKey, p.mint);
+    const { listing, escrow } = listingAddresses(p.player);
+    const price = SOL;
+
+    await send([listIx(seller, p, price)], [seller]);
+
+    const listingRent = await balance(listing);
+    const escrowRent = await balance(escrow);
+    const sellerBefore = await balance(seller.publicKey);
+    const treasuryBefore = await balance(treasury);
+    const creatorBefore = await balance(creator.authority.publicKey);
+
+    await send([buyIx(buyer, seller.publicKey, p, buyerTokenAccount, creator)], [buyer]);
+
+    const { sellerProceeds, protocolFee, royalty } = split(price, CREATOR_FEE_BPS);
+    expect(await balance(seller.publicKey)).toBe(sellerBefore + sellerProceeds + listingRent + escrowRent);
+    expect(await balance(treasury)).toBe(treasuryBefore + protocolFee);
+    expect(await balance(creator.authority.publicKey)).toBe(creatorBefore + royalty);
+
+    expect(await tokenAmount(buyerTokenAccount)).toBe(1n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(listing)).toBe(true);
+    expect((await playerOwner(p.player)).equals(buyer.publicKey)).toBe(true);
+
+    // The new owner can list straight away
+    await send([listIx(buyer, { ...p, tokenAccount: buyerTokenAccount }, 2n * SOL)], [buyer]);
+  });
+
+  test('buying a creator player without the creator accounts fails', async () => {
+    const creator = await verifiedCreator();
+    const seller = await wallet();
+    const buyer = await wallet();
+    const p = await mintPlayer(seller, 'Zeus', creator.authority);
+    const buyerTokenAccount = await tokenAccount(buyer.publicKey, p.mint);
+
+    await send([listIx(seller, p, SOL)], [seller]);
+    await expectError([buyIx(buyer, seller.publicKey, p, buyerTokenAccount)], [buyer], 'InvalidCreatorAccount');
+  });
+
+  test('purchase offers escrow lamports until withdrawn or accepted', async () => {
+    const creator = await verifiedCreator();
+    const seller = await wallet();
+    const buyer = await wallet();
+    const p = await mintPlayer(seller, 'Keria', creator.authority);
+    const buyerTokenAccount = await tokenAccount(buyer.publicKey, p.mint);
+    const offer = offerAddress(p.player, buyer.publicKey);
+
+    // Withdrawing returns the amount and the rent
+    const buyerStart = await balance(buyer.publicKey);
+    await send([makeOfferIx(buyer, p, SOL / 2n)], [buyer]);
+    const offerLamports = await balance(offer);
+    expect(await balance(buyer.publicKey)).toBe(buyerStart - offerLamports);
+
+    await send([anchorInstruction('cancel_purchase_offer', [writable(buyer.publicKey, true), writable(offer)])], [buyer]);
+    expect(await isClosed(offer)).toBe(true);
+    expect(await balance(buyer.publicKey)).toBe(buyerStart);
+
+    // A listed player is escrowed, so the owner can't accept an offer on it
+    const amount = SOL;
+    await send([makeOfferIx(buyer, p, amount), listIx(seller, p, 3n * SOL)], [buyer, seller]);
+    await expectError([acceptOfferIx(seller, buyer.publicKey, p, buyerTokenAccount, creator)], [seller], 'PlayerEscrowed');
+    await send([delistIx(seller, p)], [seller]);
+
+    const offerRent = (await balance(offer)) - amount;
+    const buyerBefore = await balance(buyer.publicKey);
+    const sellerBefore = await balance(seller.publicKey);
+    const treasuryBefore = await balance(treasury);
+    const creatorBefore = await balance(creator.authority.publicKey);
+
+    await send([acceptOfferIx(seller, buyer.publicKey, p, buyerTokenAccount, creator)], [seller]);
+
+    const { sellerProceeds, protocolFee, royalty } = split(amount, CREATOR_FEE_BPS);
+    expect(await balance(seller.publicKey)).toBe(sellerBefore + sellerProceeds);
+    expect(await balance(treasury)).toBe(treasuryBefore + protocolFee);
+    expect(await balance(creator.authority.publicKey)).toBe(creatorBefore + royalty);
+    expect(await balance(buyer.publicKey)).toBe(buyerBefore + offerRent);
+
+    expect(await isClosed(offer)).toBe(true);
+    expect(await tokenAmount(buyerTokenAccount)).toBe(1n);
+    expect(await tokenAmount(p.tokenAccount)).toBe(0n);
+    expect((await playerOwner(p.player)).equals(buyer.publicKey)).toBe(true);
+  });
+
+  // Auctions
+
+  function auctionAddresses(player: PublicKey) {
+    const [auction] = findPDA([strToBuffer('auction'), player.toBuffer()], PROGRAM_ID);
+    const [escrow] = findPDA([strToBuffer('auction_escrow'), auction.toBuffer()], PROGRAM_ID);
+    return { auction, escrow };
+  }
+
+  function createEnglishAuctionIx(seller: Keypair, p: Player, reserve: bigint, increment: bigint) {
+    const { auction, escrow } = auctionAddresses(p.player);
+    return anchorInstruction('create_auction', [
+      writable(seller.publicKey, true),
+      writable(p.player),
+      readonly(p.mint),
+      writable(p.tokenAccount),
+      writable(auction),
+      writable(escrow),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SystemProgram.programId),
+    ], borsh.u8(ENGLISH), borsh.u64(reserve), borsh.u64(0), borsh.u64(increment), borsh.i64(AUCTION_DURATION));
+  }
+
+  function bidIx(bidder: Keypair, p: Player, amount: bigint, previousBidder?: PublicKey) {
+    return anchorInstruction('place_bid', [
+      writable(bidder.publicKey, true),
+      writable(auctionAddresses(p.player).auction),
+      previousBidder ? writable(previousBidder) : absent(),
+      readonly(SystemProgram.programId),
+    ], borsh.u64(amount));
+  }
+
+  function settleIx(seller: PublicKey, p: Player, destination: PublicKey, creator?: { authority: Keypair, account: PublicKey }) {
+    const { auction, escrow } = auctionAddresses(p.player);
+    return anchorInstruction('settle_auction', [
+      writable(auction),
+      writable(p.player),
+      writable(escrow),
+      writable(destination),
+      writable(seller),
+      writable(treasury),
+      ...creatorAccounts(creator),
+      readonly(TOKEN_PROGRAM_ID),
+    ]);
+  }
+
+  function cancelAuctionIx(seller: Keypair, p: Player) {
+    const { auction, escrow } = auctionAddresses(p.player);
+    return anchorInstruction('cancel_auction', [
+      writable(seller.publicKey, true),
+      writable(auction),
+      writable(p.player),
+      writable(escrow),
+      writable(p.tokenAccount),
+      readonly(TOKEN_PROGRAM_ID),
+    ]);
+  }
+
+  test('outbid bidders are refunded and settling pays out to the winner', async () => {
+    const creator = await verifiedCreator();
+    const seller = await wallet();
+    const first = await wallet();
+    const second = await wallet();
+    const p = await mintPlayer(seller, 'Ruler', creator.authority);
+    const firstTokenAccount = await tokenAccount(first.publicKey, p.mint);
+    const secondTokenAccount = await tokenAccount(second.publicKey, p.mint);
+    const { auction, escrow } = auctionAddresses(p.player);
+
+    await send([createEnglishAuctionIx(seller, p, SOL, SOL / 10n)], [seller]);
+    expect(await tokenAmount(escrow)).toBe(1n);
+    await expectError([listIx(seller, p, SOL)], [seller], 'PlayerEscrowed');
+
+    // Outbidding refunds the previous high bid in full
+    const firstBefore = await balance(first.publicKey);
+    await send([bidIx(first, p, SOL)], [first]);
+    expect(await balance(first.publicKey)).toBe(firstBefore - SOL);
+
+    const winningBid = SOL + SOL / 5n;
+    await send([bidIx(second, p, winningBid, first.publicKey)], [second]);
+    expect(await balance(first.publicKey)).toBe(firstBefore);
+
+    await expectError([cancelAuctionIx(seller, p)], [seller], 'AuctionHasBids');
+    await expectError([settleIx(seller.publicKey, p, secondTokenAccount, creator)], [], 'AuctionNotEnded');
+
+    await advanceClock(AUCTION_DURATION);
+
+    // Only the highest bidder can receive the NFT
+    await expectError([settleIx(seller.publicKey, p, firstTokenAccount, creator)], [], 'InvalidBidder');
+
+    const auctionRent = (await balance(auction)) - winningBid;
+    const escrowRent = await balance(escrow);
+    const sellerBefore = await balance(seller.publicKey);
+    const treasuryBefore = await balance(treasury);
+    const creatorBefore = await balance(creator.authority.publicKey);
+
+    await send([settleIx(seller.publicKey, p, secondTokenAccount, creator)]);
+
+    const { sellerProceeds, protocolFee, royalty } = split(winningBid, CREATOR_FEE_BPS);
+    expect(await balance(seller.publicKey)).toBe(sellerBefore + sellerProceeds + auctionRent + escrowRent);
+    expect(await balance(treasury)).toBe(treasuryBefore + protocolFee);
+    expect(await balance(creator.authority.publicKey)).toBe(creatorBefore + royalty);
+
+    expect(await tokenAmount(secondTokenAccount)).toBe(1n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(auction)).toBe(true);
+    expect((await playerOwner(p.player)).equals(second.publicKey)).toBe(true);
+  });
+
+  test('an auction without bids returns the NFT on settle or cancel', async () => {
+    const seller = await wallet();
+    const p = await mintPlayer(seller, 'Knight');
+    const { auction, escrow } = auctionAddresses(p.player);
+
+    await send([createEnglishAuctionIx(seller, p, SOL, SOL / 10n)], [seller]);
+    await advanceClock(AUCTION_DURATION);
+
+    const sellerBefore = await balance(seller.publicKey);
+    const rent = (await balance(auction)) + (await balance(escrow));
+    await send([settleIx(seller.publicKey, p, p.tokenAccount)]);
+
+    expect(await tokenAmount(p.tokenAccount)).toBe(1n);
+    expect(await balance(seller.publicKey)).toBe(sellerBefore + rent);
+    expect(await isClosed(escrow)).toBe(true);
+    expect((await playerOwner(p.player)).equals(seller.publicKey)).toBe(true);
+
+    // Released from escrow, the player can go up again and be called off
+    await send([createEnglishAuctionIx(seller, p, 2n * SOL, SOL / 10n)], [seller]);
+    expect(await tokenAmount(escrow)).toBe(1n);
+
+    await send([cancelAuctionIx(seller, p)], [seller]);
+    expect(await tokenAmount(p.tokenAccount)).toBe(1n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(auction)).toBe(true);
+
+    await send([listIx(seller, p, SOL)], [seller]);
+  });
+
+  // Trades
+
+  function tradeAddresses(proposer: PublicKey, tradeId: bigint, mint: PublicKey) {
+    const [trade] = findPDA([strToBuffer('trade'), proposer.toBuffer(), borsh.u64(tradeId)], PROGRAM_ID);
+    const [escrow] = findPDA([strToBuffer('trade_escrow'), trade.toBuffer(), mint.toBuffer()], PROGRAM_ID);
+    return { trade, escrow };
+  }
+
+  // Proposer offers `offered` plus lamports for the counterparty's `requested`
+  function proposeIx(proposer: Keypair, counterparty: PublicKey, tradeId: bigint, offered: Player, requested: Player, lamports: bigint) {
+    const { trade, escrow } = tradeAddresses(proposer.publicKey, tradeId, offered.mint);
+    return anchorInstruction('propose_trade', [
+      writable(proposer.publicKey, true),
+      readonly(counterparty),
+      writable(trade),
+      absent(),
+      absent(),
+      absent(),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SystemProgram.programId),
+      writable(offered.player),
+      readonly(offered.mint),
+      writable(offered.tokenAccount),
+      writable(escrow),
+    ], borsh.u64(tradeId), borsh.vec([requested.player.toBuffer()]), borsh.u64(lamports), borsh.u64(0), borsh.u64(0), borsh.u64(0));
+  }
+
+  test('accepting a trade swaps the players and releases the escrow', async () => {
+    const proposer = await wallet();
+    const counterparty = await wallet();
+    const offered = await mintPlayer(proposer, 'Oner');
+    const requested = await mintPlayer(counterparty, 'Peanut');
+    const counterpartyOfferedAccount = await tokenAccount(counterparty.publicKey, offered.mint);
+    const proposerRequestedAccount = await tokenAccount(proposer.publicKey, requested.mint);
+    const { trade, escrow } = tradeAddresses(proposer.publicKey, 1n, offered.mint);
+    const sweetener = SOL / 10n;
+
+    await send([proposeIx(proposer, counterparty.publicKey, 1n, offered, requested, sweetener)], [proposer]);
+    expect(await tokenAmount(escrow)).toBe(1n);
+    await expectError([listIx(proposer, offered, SOL)], [proposer], 'PlayerEscrowed');
+
+    const tradeRent = (await balance(trade)) - sweetener;
+    const escrowRent = await balance(escrow);
+    const proposerBefore = await balance(proposer.publicKey);
+    const counterpartyBefore = await balance(counterparty.publicKey);
+
+    await send([anchorInstruction('accept_trade', [
+      writable(counterparty.publicKey, true),
+      writable(trade),
+      writable(proposer.publicKey),
+      absent(),
+      absent(),
+      absent(),
+      readonly(TOKEN_PROGRAM_ID),
+      readonly(SystemProgram.programId),
+      writable(offered.player),
+      writable(escrow),
+      writable(counterpartyOfferedAccount),
+      absent(),
+      absent(),
+      writable(requested.player),
+      writable(requested.tokenAccount),
+      writable(proposerRequestedAccount),
+      absent(),
+      absent(),
+    ])], [counterparty]);
+
+    expect(await tokenAmount(counterpartyOfferedAccount)).toBe(1n);
+    expect(await tokenAmount(proposerRequestedAccount)).toBe(1n);
+    expect(await tokenAmount(requested.tokenAccount)).toBe(0n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(trade)).toBe(true);
+    expect((await playerOwner(offered.player)).equals(counterparty.publicKey)).toBe(true);
+    expect((await playerOwner(requested.player)).equals(proposer.publicKey)).toBe(true);
+
+    expect(await balance(counterparty.publicKey)).toBe(counterpartyBefore + sweetener);
+    expect(await balance(proposer.publicKey)).toBe(proposerBefore + tradeRent + escrowRent);
+
+    // The traded player is no longer escrowed
+    await send([listIx(counterparty, { ...offered, tokenAccount: counterpartyOfferedAccount }, SOL)], [counterparty]);
+  });
+
+  test('canceling a trade returns the escrowed player and lamports to the proposer', async () => {
+    const proposer = await wallet();
+    const counterparty = await wallet();
+    const stranger = await wallet();
+    const offered = await mintPlayer(proposer, 'Gumayusi');
+    const requested = await mintPlayer(counterparty, 'Viper');
+    const { trade, escrow } = tradeAddresses(proposer.publicKey, 2n, offered.mint);
+
+    await send([proposeIx(proposer, counterparty.publicKey, 2n, offered, requested, SOL / 10n)], [proposer]);
+
+    const cancelIx = (caller: Keypair) => anchorInstruction('cancel_trade', [
+      readonly(caller.publicKey, true),
+      writable(trade),
+      writable(proposer.publicKey),
+      absent(),
+      absent(),
+      readonly(TOKEN_PROGRAM_ID),
+      writable(offered.player),
+      writable(escrow),
+      writable(offered.tokenAccount),
+    ]);
+
+    await expectError([cancelIx(stranger)], [stranger], 'UnauthorizedAccess');
+
+    // The counterparty rejecting it hands back everything, rent included
+    const escrowed = (await balance(trade)) + (await balance(escrow));
+    const proposerBefore = await balance(proposer.publicKey);
+
+    await send([cancelIx(counterparty)], [counterparty]);
+
+    expect(await tokenAmount(offered.tokenAccount)).toBe(1n);
+    expect(await isClosed(escrow)).toBe(true);
+    expect(await isClosed(trade)).toBe(true);
+    expect(await balance(proposer.publicKey)).toBe(proposerBefore + escrowed);
+    expect((await playerOwner(offered.player)).equals(proposer.publicKey)).toBe(true);
+
+    await send([listIx(proposer, offered, SOL)], [proposer]);
+  });
+});
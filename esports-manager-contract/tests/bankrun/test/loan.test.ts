@@ -0,0 +1,126 @@
+// Loan roster lifecycle: proposal, acceptance onto the borrowing roster and return to the parent team.
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { PROGRAM_ID, anchorInstruction, borsh, findPDA, readonly, strToBuffer, writable } from '../src/helper';
+import { Harness, JUNGLE, MID, Player, SOL, TOP } from '../src/harness';
+
+const LOAN_FEE = SOL / 10n;
+const LOAN_DURATION = 7n * 86400n;
+
+describe('Player loans', () => {
+  let h: Harness;
+  let parentOwner: Keypair;
+  let borrowerOwner: Keypair;
+  let parentTeam: PublicKey;
+  let borrowerTeam: PublicKey;
+  let loaned: Player;
+  let parentTeammate: Player;
+  let borrowerTeammate: Player;
+
+  // Parent team fields `loaned` (mid) and `parentTeammate` (top); the borrower fields `borrowerTeammate` (jungle)
+  beforeEach(async () => {
+    h = await Harness.start();
+    parentOwner = await h.wallet();
+    borrowerOwner = await h.wallet();
+
+    loaned = await h.mintPlayer(parentOwner, 'Ace');
+    parentTeammate = await h.mintPlayer(parentOwner, 'Bolt', TOP);
+    parentTeam = await h.createTeam(parentOwner, 'Parent');
+    await h.addPlayerToTeam(parentOwner, parentTeam, loaned, MID, 0n, 4);
+    await h.addPlayerToTeam(parentOwner, parentTeam, parentTeammate, TOP, 0n, 4, [loaned]);
+
+    borrowerTeammate = await h.mintPlayer(borrowerOwner, 'Cove', JUNGLE);
+    borrowerTeam = await h.createTeam(borrowerOwner, 'Borrower');
+    await h.addPlayerToTeam(borrowerOwner, borrowerTeam, borrowerTeammate, JUNGLE, 0n, 4);
+  });
+
+  function loanAddress(player: PublicKey): PublicKey {
+    return findPDA([strToBuffer('loan'), player.toBuffer()], PROGRAM_ID)[0];
+  }
+
+  function proposeIx(fee = LOAN_FEE) {
+    return anchorInstruction('propose_loan', [
+      writable(parentOwner.publicKey, true),
+      readonly(parentTeam),
+      readonly(borrowerTeam),
+      readonly(loaned.player),
+      writable(loanAddress(loaned.player)),
+      readonly(SystemProgram.programId),
+    ], borsh.u8(MID), borsh.u64(fee), borsh.i64(LOAN_DURATION));
+  }
+
+  function acceptIx(borrower: Keypair) {
+    return anchorInstruction('accept_loan', [
+      writable(borrower.publicKey, true),
+      writable(loanAddress(loaned.player)),
+      writable(borrowerTeam),
+      readonly(parentTeam),
+      writable(loaned.player),
+      readonly(SystemProgram.programId),
+      readonly(borrowerTeammate.player),
+    ]);
+  }
+
+  function returnIx(remaining: Player[]) {
+    return anchorInstruction('return_loaned_player', [
+      readonly(h.context.payer.publicKey, true),
+      writable(loanAddress(loaned.player)),
+      writable(borrowerTeam),
+      writable(parentTeam),
+      writable(loaned.player),
+      writable(parentOwner.publicKey),
+      ...remaining.map(p => readonly(p.player)),
+    ]);
+  }
+
+  async function rosterOf(team: PublicKey): Promise<string[]> {
+    return (await h.roster(team)).map(mint => mint.toBase58());
+  }
+
+  test('a loaned player joins the borrowing roster, keeps the parent slot and returns when the loan ends', async () => {
+    await h.send([proposeIx()], [parentOwner]);
+
+    // Only the borrowing team's owner can accept
+    await h.expectError([acceptIx(parentOwner)], [parentOwner], 'UnauthorizedAccess');
+
+    await h.send([acceptIx(borrowerOwner)], [borrowerOwner]);
+    expect(await rosterOf(borrowerTeam)).toEqual([borrowerTeammate.mint.toBase58(), loaned.mint.toBase58()]);
+    expect(await rosterOf(parentTeam)).toEqual([loaned.mint.toBase58(), parentTeammate.mint.toBase58()]);
+    expect((await h.playerTeam(loaned.player))?.toBase58()).toBe(borrowerTeam.toBase58());
+
+    await h.expectError([returnIx([borrowerTeammate, parentTeammate])], [], 'LoanNotEnded');
+
+    await h.advanceClock(LOAN_DURATION);
+
+    // Both rosters are refreshed, so the parent's other players must be passed too
+    await h.expectError([returnIx([borrowerTeammate])], [], 'InvalidRosterAccounts');
+
+    const loanRent = await h.balance(loanAddress(loaned.player));
+    const before = await h.balance(parentOwner.publicKey);
+    await h.send([returnIx([borrowerTeammate, parentTeammate])]);
+
+    // The escrowed fee and the loan account's rent go to the parent owner
+    expect(await h.isClosed(loanAddress(loaned.player))).toBe(true);
+    expect(await h.balance(parentOwner.publicKey)).toBe(before + loanRent);
+    expect(loanRent).toBeGreaterThan(LOAN_FEE);
+
+    expect(await rosterOf(borrowerTeam)).toEqual([borrowerTeammate.mint.toBase58()]);
+    expect(await rosterOf(parentTeam)).toEqual([loaned.mint.toBase58(), parentTeammate.mint.toBase58()]);
+    expect((await h.playerTeam(loaned.player))?.toBase58()).toBe(parentTeam.toBase58());
+  });
+
+  test('a proposed loan can be canceled, but not once accepted', async () => {
+    const cancelIx = anchorInstruction('cancel_loan', [
+      writable(parentOwner.publicKey, true),
+      writable(loanAddress(loaned.player)),
+    ]);
+
+    await h.send([proposeIx()], [parentOwner]);
+    await h.send([cancelIx], [parentOwner]);
+    expect(await h.isClosed(loanAddress(loaned.player))).toBe(true);
+
+    // A different fee keeps the second proposal from repeating the first transaction
+    await h.send([proposeIx(0n)], [parentOwner]);
+    await h.send([acceptIx(borrowerOwner)], [borrowerOwner]);
+    await h.expectError([cancelIx], [parentOwner], 'LoanAlreadyActive');
+  });
+});
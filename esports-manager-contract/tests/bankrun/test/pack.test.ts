@@ -0,0 +1,145 @@
+// Pack purchase and the slot-hash reveal that mints the pulled players.
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { PROGRAM_ID, anchorInstruction, borsh, findPDA, readonly, strToBuffer, writable } from '../src/helper';
+import { Harness, SOL } from '../src/harness';
+
+const PACK_ID = 1;
+const PACK_PRICE = SOL / 2n;
+const PLAYERS_PER_PACK = 2;
+const ALL_COMMON = [10_000, 0, 0, 0, 0];
+
+describe('Packs', () => {
+  let h: Harness;
+  let admin: Keypair;
+  let config: PublicKey;
+  let treasury: PublicKey;
+  const [packDefinition] = findPDA([strToBuffer('pack'), u32(PACK_ID)], PROGRAM_ID);
+
+  beforeEach(async () => {
+    h = await Harness.start();
+    admin = await h.wallet();
+    config = await h.setConfig(admin.publicKey);
+    [treasury] = findPDA([strToBuffer('treasury')], PROGRAM_ID);
+  });
+
+  function u32(value: number): Buffer {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(value, 0);
+    return buf;
+  }
+
+  function createPackIx(authority: Keypair) {
+    return anchorInstruction('create_pack_definition', [
+      writable(authority.publicKey, true),
+      readonly(config),
+      writable(packDefinition),
+      readonly(SystemProgram.programId),
+      readonly(SYSVAR_RENT_PUBKEY),
+    ],
+      u32(PACK_ID),
+      borsh.string('Starter'),
+      borsh.u64(PACK_PRICE),
+      borsh.u8(PLAYERS_PER_PACK),
+      Buffer.concat(ALL_COMMON.map(borsh.u16)),
+    );
+  }
+
+  async function openPack(buyer: Keypair, packsOpened: number): Promise<PublicKey> {
+    const [packOpening] = findPDA([strToBuffer('pack_opening'), packDefinition.toBuffer(), u32(packsOpened)], PROGRAM_ID);
+    await h.send([anchorInstruction('open_pack', [
+      writable(buyer.publicKey, true),
+      writable(packDefinition),
+      writable(packOpening),
+      writable(treasury),
+      readonly(SystemProgram.programId),
+    ])], [buyer]);
+    return packOpening;
+  }
+
+  function playerAddress(mint: PublicKey): PublicKey {
+    return findPDA([strToBuffer('player'), mint.toBuffer()], PROGRAM_ID)[0];
+  }
+
+  function revealIx(buyer: Keypair, packOpening: PublicKey, pulls: { mint: PublicKey, player: PublicKey }[], uris = pulls.map((_, i) => `https://5vs5.gg/pack/${i}.json`)) {
+    return anchorInstruction('reveal_pack', [
+      writable(buyer.publicKey, true),
+      writable(packOpening),
+      readonly(SYSVAR_SLOT_HASHES_PUBKEY),
+      readonly(SystemProgram.programId),
+      readonly(TOKEN_PROGRAM_ID),
+      ...pulls.flatMap(pull => [readonly(pull.mint), writable(pull.player)]),
+    ], borsh.vec(uris.map(borsh.string)));
+  }
+
+  async function pulls(buyer: Keypair): Promise<{ mint: PublicKey, player: PublicKey }[]> {
+    const result: { mint: PublicKey, player: PublicKey }[] = [];
+    for (let i = 0; i < PLAYERS_PER_PACK; i++) {
+      const mint = await h.mint(buyer.publicKey);
+      result.push({ mint, player: playerAddress(mint) });
+    }
+    return result;
+  }
+
+  test('only the config authority can create packs', async () => {
+    const stranger = await h.wallet();
+    await h.expectError([createPackIx(stranger)], [stranger], 'UnauthorizedAccess');
+    await h.send([createPackIx(admin)], [admin]);
+    expect(await h.isClosed(packDefinition)).toBe(false);
+  });
+
+  test('a pack is paid on opening and revealed from a later slot into one player per pull', async () => {
+    await h.send([createPackIx(admin)], [admin]);
+    const buyer = await h.wallet();
+
+    const treasuryBefore = await h.balance(treasury);
+    const packOpening = await openPack(buyer, 0);
+    expect(await h.balance(treasury)).toBe(treasuryBefore + PACK_PRICE);
+
+    // The purchase slot's hash doesn't exist yet
+    const pulled = await pulls(buyer);
+    await h.expectError([revealIx(buyer, packOpening, pulled)], [buyer], 'PackNotRevealable');
+
+    await h.advanceSlots(1n);
+    await h.send([revealIx(buyer, packOpening, pulled)], [buyer]);
+
+    expect(await h.isClosed(packOpening)).toBe(true);
+    for (const [i, pull] of pulled.entries()) {
+      const data = await h.data(pull.player);
+      expect(new PublicKey(data.subarray(8, 40)).toBase58()).toBe(buyer.publicKey.toBase58());
+      expect(new PublicKey(data.subarray(40, 72)).toBase58()).toBe(pull.mint.toBase58());
+      expect(data.subarray(76, 76 + data.readUInt32LE(72)).toString()).toBe(`Prospect #${i + 1}`);
+    }
+
+    // The next pack continues the serials
+    const nextOpening = await openPack(buyer, 1);
+    const nextPulled = await pulls(buyer);
+    await h.advanceSlots(1n);
+    await h.send([revealIx(buyer, nextOpening, nextPulled)], [buyer]);
+    const data = await h.data(nextPulled[0].player);
+    expect(data.subarray(76, 76 + data.readUInt32LE(72)).toString()).toBe(`Prospect #${PLAYERS_PER_PACK + 1}`);
+  });
+
+  test('reveal rejects other buyers and accounts that do not match the pack', async () => {
+    await h.send([createPackIx(admin)], [admin]);
+    const buyer = await h.wallet();
+    const stranger = await h.wallet();
+    const packOpening = await openPack(buyer, 0);
+    const pulled = await pulls(buyer);
+    await h.advanceSlots(1n);
+
+    await h.expectError([revealIx(stranger, packOpening, pulled)], [stranger], 'UnauthorizedAccess');
+    await h.expectError([revealIx(buyer, packOpening, pulled.slice(1), ['https://5vs5.gg/pack/0.json'])], [buyer], 'InvalidPackAccounts');
+    await h.expectError([revealIx(buyer, packOpening, pulled, ['https://5vs5.gg/pack/0.json'])], [buyer], 'InvalidPackAccounts');
+
+    // Player accounts must be the PDAs of their mints
+    const swapped = [
+      { mint: pulled[0].mint, player: pulled[1].player },
+      { mint: pulled[1].mint, player: pulled[0].player },
+    ];
+    await h.expectError([revealIx(buyer, packOpening, swapped)], [buyer], 'InvalidPackAccounts');
+
+    await h.send([revealIx(buyer, packOpening, pulled)], [buyer]);
+    expect(await h.isClosed(packOpening)).toBe(true);
+  });
+});